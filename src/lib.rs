@@ -16,5 +16,11 @@ mod vars;
 mod tests;
 
 pub use crate::model::Model;
+pub use crate::props::{Dfa, Propagate, Prune, Relation};
+pub use crate::search::{
+    Brancher, CancellationToken, Decision, Lns, Neighborhood, RandomNeighborhood, Report,
+    RestartSchedule, SearchLimits, SearchStatus, Solutions, SpaceView, Statistics, ValueSelection,
+    ValueSelector, VariableSelection,
+};
 pub use crate::solution::Solution;
-pub use crate::vars::{VarId, VarIdBinary};
+pub use crate::vars::{Domain, VarId, VarIdBinary};
@@ -1,5 +1,12 @@
-use crate::props::Propagators;
-use crate::search::{mode, search};
+use std::sync::Arc;
+
+use crate::props::{
+    mul_bounds, saturate, Dfa, Propagate, Propagators, Reification, Relation, Task,
+};
+use crate::search::{
+    self, mode, search, Brancher, CancellationToken, Config, Lns, Report, RestartSchedule,
+    SearchLimits, Solutions, ValueSelection, VariableSelection,
+};
 use crate::solution::Solution;
 use crate::vars::{VarId, VarIdBinary, Vars};
 use crate::views::{View, ViewExt};
@@ -131,10 +138,11 @@ use crate::views::{View, ViewExt};
 /// ```
 ///
 /// Find the full code in the [examples directory](https://github.com/ffmins/copper/examples/pc.rs).
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Model {
     vars: Vars,
     props: Propagators,
+    config: Config,
 }
 
 impl Model {
@@ -168,6 +176,22 @@ impl Model {
         }
     }
 
+    /// Create a new integer decision variable, whose domain is the provided list of values.
+    ///
+    /// Duplicate values are ignored, and the list does not need to be sorted.
+    /// This function will only create a decision variable if at least two values are distinct.
+    pub fn new_var_from_values(&mut self, values: &[i32]) -> Option<VarId> {
+        let mut values = values.to_vec();
+        values.sort_unstable();
+        values.dedup();
+
+        if values.len() > 1 {
+            Some(self.new_var_from_values_unchecked(&values))
+        } else {
+            None
+        }
+    }
+
     /// Create a new binary decision variable.
     pub fn new_var_binary(&mut self) -> VarIdBinary {
         VarIdBinary(self.new_var_unchecked(0, 1))
@@ -188,6 +212,14 @@ impl Model {
         self.vars.new_var_with_bounds(min, max)
     }
 
+    /// Create a new integer decision variable, whose domain is the provided list of values.
+    ///
+    /// This function assumes that values are sorted, unique, and that there is at least one.
+    fn new_var_from_values_unchecked(&mut self, values: &[i32]) -> VarId {
+        self.props.on_new_var();
+        self.vars.new_var_with_values(values)
+    }
+
     /// Create an expression of two views added together.
     pub fn add(&mut self, x: impl View, y: impl View) -> VarId {
         let min = x.min_raw(&self.vars) + y.min_raw(&self.vars);
@@ -199,6 +231,89 @@ impl Model {
         s
     }
 
+    /// Create an expression of two views multiplied together.
+    pub fn mul(&mut self, x: impl View, y: impl View) -> VarId {
+        let x_bounds = (x.min_raw(&self.vars).into(), x.max_raw(&self.vars).into());
+        let y_bounds = (y.min_raw(&self.vars).into(), y.max_raw(&self.vars).into());
+
+        // Product is computed without overflow, then saturated to fit in a decision variable
+        let (min, max) = mul_bounds(x_bounds, y_bounds);
+        let s = self.new_var_unchecked(saturate(min), saturate(max));
+
+        let _p = self.props.mul(x, y, s);
+
+        s
+    }
+
+    /// Create an expression of the division of two views, rounding towards zero like `/`.
+    ///
+    /// Division by zero is not allowed: the divisor cannot take the value zero.
+    pub fn div(&mut self, x: impl View, y: impl View) -> VarId {
+        let max = self.get_abs_max(x);
+        let s = self.new_var_unchecked(-max, max);
+
+        let _p = self.props.div(x, y, s);
+
+        s
+    }
+
+    /// Create an expression of the remainder of the division of two views, like `%`.
+    ///
+    /// The remainder has the sign of the dividend. The divisor cannot take the value zero.
+    pub fn modulo(&mut self, x: impl View, y: impl View) -> VarId {
+        let min = core::cmp::min(x.min_raw(&self.vars), 0);
+        let max = core::cmp::max(x.max_raw(&self.vars), 0);
+        let s = self.new_var_unchecked(min, max);
+
+        let _p = self.props.modulo(x, y, s);
+
+        s
+    }
+
+    /// Create an expression of the absolute value of a view.
+    pub fn abs(&mut self, x: impl View) -> VarId {
+        let max = self.get_abs_max(x);
+        let s = self.new_var_unchecked(0, max);
+
+        let _p = self.props.abs(x, s);
+
+        s
+    }
+
+    /// Largest absolute value a view can take, saturated to fit in a decision variable.
+    fn get_abs_max(&self, x: impl View) -> i32 {
+        let min = i64::from(x.min_raw(&self.vars));
+        let max = i64::from(x.max_raw(&self.vars));
+
+        saturate(min.abs().max(max.abs()))
+    }
+
+    /// Create an expression of the minimum of a slice of views.
+    ///
+    /// The minimum of an empty slice is undefined, and makes the model infeasible.
+    pub fn min(&mut self, xs: &[impl View]) -> VarId {
+        let min = xs.iter().map(|x| x.min_raw(&self.vars)).min().unwrap_or(0);
+        let max = xs.iter().map(|x| x.max_raw(&self.vars)).min().unwrap_or(0);
+        let s = self.new_var_unchecked(min, max);
+
+        let _p = self.props.min(xs.to_vec(), s);
+
+        s
+    }
+
+    /// Create an expression of the maximum of a slice of views.
+    ///
+    /// The maximum of an empty slice is undefined, and makes the model infeasible.
+    pub fn max(&mut self, xs: &[impl View]) -> VarId {
+        let min = xs.iter().map(|x| x.min_raw(&self.vars)).max().unwrap_or(0);
+        let max = xs.iter().map(|x| x.max_raw(&self.vars)).max().unwrap_or(0);
+        let s = self.new_var_unchecked(min, max);
+
+        let _p = self.props.max(xs.to_vec(), s);
+
+        s
+    }
+
     /// Create an expression of the sum of a slice of views.
     pub fn sum(&mut self, xs: &[impl View]) -> VarId {
         self.sum_iter(xs.iter().copied())
@@ -217,11 +332,167 @@ impl Model {
         s
     }
 
+    /// Declare constraint comparing a weighted sum of views to a constant: `sum(a * x) <op> rhs`.
+    ///
+    /// Unlike combining [`Self::sum_iter`] with a comparison, this does not create an
+    /// intermediate variable for the sum.
+    pub fn linear(&mut self, terms: &[(i32, impl View)], relation: Relation, rhs: i32) {
+        let xs: Vec<_> = terms.iter().map(|&(a, x)| x.times(a)).collect();
+        let _p = self.props.linear(xs, relation, rhs);
+    }
+
+    /// Create an expression of the value of a constant array at a variable index: `array[index]`.
+    ///
+    /// The index is zero-based, and restricted to valid positions of the array.
+    pub fn element(&mut self, index: VarId, array: &[i32]) -> VarId {
+        let mut values = array.to_vec();
+        values.sort_unstable();
+        values.dedup();
+
+        // An empty array leaves no valid index, the domain of the expression does not matter
+        let s = if values.is_empty() {
+            self.new_var_unchecked(0, 0)
+        } else {
+            self.new_var_from_values_unchecked(&values)
+        };
+
+        let _p = self.props.element(index, array.to_vec(), s);
+
+        s
+    }
+
+    /// Create an expression of the value of an array of views at a variable index: `array[index]`.
+    ///
+    /// The index is zero-based, and restricted to valid positions of the array.
+    pub fn element_var(&mut self, index: VarId, array: &[impl View]) -> VarId {
+        let min = array
+            .iter()
+            .map(|x| x.min_raw(&self.vars))
+            .min()
+            .unwrap_or(0);
+        let max = array
+            .iter()
+            .map(|x| x.max_raw(&self.vars))
+            .max()
+            .unwrap_or(0);
+        let s = self.new_var_unchecked(min, max);
+
+        let _p = self.props.element_var(index, array.to_vec(), s);
+
+        s
+    }
+
+    /// Declare all expressions of a slice to take pairwise distinct values.
+    ///
+    /// Propagation reasons on domain bounds only, which is fast but may miss some deductions.
+    pub fn all_different(&mut self, xs: &[impl View]) {
+        let _p = self.props.all_different(xs.to_vec());
+    }
+
+    /// Declare all expressions of a slice to take pairwise distinct values.
+    ///
+    /// Propagation removes every value that cannot be part of a solution of this constraint.
+    /// It is stronger than [all_different](Self::all_different), but more costly on large domains.
+    pub fn all_different_domain(&mut self, xs: &[impl View]) {
+        let _p = self.props.all_different_domain(xs.to_vec());
+    }
+
+    /// Declare successor variables to form a single cycle that visits every node.
+    ///
+    /// Node `i` is followed by node `xs[i]`, with zero-based indices. Propagation eliminates
+    /// subtours and removes arcs between strongly connected components of the remaining graph.
+    /// Successors are also declared [all different](Self::all_different_domain).
+    pub fn circuit(&mut self, xs: &[VarId]) {
+        let _p = self.props.all_different_domain(xs.to_vec());
+        let _p = self.props.circuit(xs.to_vec());
+    }
+
+    /// Declare successor variables to form a single cycle, over a subset of the nodes.
+    ///
+    /// Node `i` is left out of the cycle when `xs[i] == i`, other nodes are followed by node
+    /// `xs[i]` as in [circuit](Self::circuit). Leaving out all nodes is allowed.
+    pub fn subcircuit(&mut self, xs: &[VarId]) {
+        let _p = self.props.all_different_domain(xs.to_vec());
+        let _p = self.props.subcircuit(xs.to_vec());
+    }
+
+    /// Restrict decision variables to take the values of one of the allowed tuples.
+    ///
+    /// Tuples are rows of a compatibility matrix, with one column per variable. Tuples whose
+    /// length differs from the number of variables are ignored.
+    pub fn table(&mut self, xs: &[VarId], tuples: &[Vec<i32>]) {
+        let _p = self.props.table(xs.to_vec(), tuples);
+    }
+
+    /// Forbid decision variables from taking the values of any of the provided tuples.
+    ///
+    /// Tuples whose length differs from the number of variables are ignored.
+    pub fn negative_table(&mut self, xs: &[VarId], tuples: &[Vec<i32>]) {
+        let _p = self.props.negative_table(xs.to_vec(), tuples);
+    }
+
+    /// Restrict a sequence of decision variables to the words accepted by an automaton.
+    ///
+    /// Each variable holds a value read by the automaton, in order. It is a natural way to
+    /// express sequence rules, such as patterns of shifts in a schedule.
+    pub fn regular(&mut self, xs: &[VarId], automaton: Dfa) {
+        let _p = self.props.regular(xs.to_vec(), automaton);
+    }
+
+    /// Schedule tasks on a resource, so that their total demand never exceeds its capacity.
+    ///
+    /// Task `i` starts at `starts[i]`, runs for `durations[i]` and uses `demands[i]` of the
+    /// resource while running. Extra entries of the longer slices are ignored.
+    /// Propagation relies on time-tabling, which reasons on the parts of tasks that are
+    /// guaranteed to run at a given time.
+    pub fn cumulative(
+        &mut self,
+        starts: &[impl View],
+        durations: &[i32],
+        demands: &[i32],
+        capacity: i32,
+    ) {
+        let _p = self
+            .props
+            .cumulative(tasks(starts, durations, demands), capacity);
+    }
+
+    /// Schedule tasks on a resource, so that their total demand never exceeds its capacity.
+    ///
+    /// Propagation adds edge-finding to [cumulative](Self::cumulative), which detects tasks
+    /// that must end after a group of other tasks. It is stronger, but more costly.
+    pub fn cumulative_edge_finding(
+        &mut self,
+        starts: &[impl View],
+        durations: &[i32],
+        demands: &[i32],
+        capacity: i32,
+    ) {
+        let tasks = tasks(starts, durations, demands);
+
+        let _p = self.props.cumulative(tasks.clone(), capacity);
+        let _p = self.props.cumulative_edge_finding(tasks, capacity);
+    }
+
+    /// Schedule tasks on a machine that runs one of them at a time.
+    ///
+    /// Task `i` starts at `starts[i]` and runs for `durations[i]`, extra entries of the longer
+    /// slice are ignored. Propagation relies on edge-finding, detectable precedences and
+    /// not-first/not-last rules, which are much stronger than posting pairwise disjunctions.
+    pub fn disjunctive(&mut self, starts: &[impl View], durations: &[i32]) {
+        let _p = self.props.disjunctive(starts.to_vec(), durations.to_vec());
+    }
+
     /// Declare two expressions to be equal.
     pub fn equals(&mut self, x: impl View, y: impl View) {
         let _p = self.props.equals(x, y);
     }
 
+    /// Declare two expressions to be different.
+    pub fn not_equals(&mut self, x: impl View, y: impl View) {
+        let _p = self.props.not_equals(x, y);
+    }
+
     /// Declare constraint `x <= y`.
     pub fn less_than_or_equals(&mut self, x: impl View, y: impl View) {
         let _p = self.props.less_than_or_equals(x, y);
@@ -242,17 +513,242 @@ impl Model {
         let _p = self.props.greater_than(x, y);
     }
 
+    /// Declare constraint that at least one of `pos` is set, or at least one of `neg` is not.
+    pub fn clause(&mut self, pos: &[VarIdBinary], neg: &[VarIdBinary]) {
+        let pos = pos.iter().map(|&x| (x, true));
+        let neg = neg.iter().map(|&x| (x, false));
+
+        let _p = self.props.clause(pos.chain(neg).collect());
+    }
+
+    /// Declare constraint that `y` must be set whenever `x` is.
+    pub fn implies(&mut self, x: VarIdBinary, y: VarIdBinary) {
+        self.clause(&[y], &[x]);
+    }
+
+    /// Create a binary expression that is set if and only if `x` is not.
+    pub fn not(&mut self, x: VarIdBinary) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.not(x, b);
+        b
+    }
+
+    /// Create a binary expression that is set if and only if all of `xs` are set.
+    pub fn and(&mut self, xs: &[VarIdBinary]) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.and(xs.to_vec(), b);
+        b
+    }
+
+    /// Create a binary expression that is set if and only if at least one of `xs` is set.
+    pub fn or(&mut self, xs: &[VarIdBinary]) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.or(xs.to_vec(), b);
+        b
+    }
+
+    /// Create a binary expression that is set if and only if exactly one of `x` and `y` is set.
+    pub fn xor(&mut self, x: VarIdBinary, y: VarIdBinary) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.xor(x, y, b);
+        b
+    }
+
+    /// Declare a user-defined propagator, to enforce constraints not provided by the crate.
+    ///
+    /// The propagator is scheduled whenever the domain of one of its trigger variables changes.
+    /// It must not prune values of assignments that satisfy its constraint, and must fail the
+    /// space once all its trigger variables are assigned to values that violate it.
+    pub fn post_propagator(&mut self, propagator: impl Propagate) {
+        let _p = self.props.custom(propagator);
+    }
+
+    /// Create a binary variable that is set if and only if constraint `x == y` holds.
+    pub fn reify_equals(&mut self, x: impl View, y: impl View) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.reify_equals(b, x, y, Reification::Full);
+        b
+    }
+
+    /// Create a binary variable that is set if and only if constraint `x != y` holds.
+    pub fn reify_not_equals(&mut self, x: impl View, y: impl View) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.reify_not_equals(b, x, y, Reification::Full);
+        b
+    }
+
+    /// Create a binary variable that is set if and only if constraint `x <= y` holds.
+    pub fn reify_less_than_or_equals(&mut self, x: impl View, y: impl View) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self
+            .props
+            .reify_less_than_or_equals(b, x, y, Reification::Full);
+        b
+    }
+
+    /// Create a binary variable that is set if and only if constraint `x < y` holds.
+    pub fn reify_less_than(&mut self, x: impl View, y: impl View) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.reify_less_than(b, x, y, Reification::Full);
+        b
+    }
+
+    /// Create a binary variable that is set if and only if constraint `x >= y` holds.
+    pub fn reify_greater_than_or_equals(&mut self, x: impl View, y: impl View) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self
+            .props
+            .reify_greater_than_or_equals(b, x, y, Reification::Full);
+        b
+    }
+
+    /// Create a binary variable that is set if and only if constraint `x > y` holds.
+    pub fn reify_greater_than(&mut self, x: impl View, y: impl View) -> VarIdBinary {
+        let b = self.new_var_binary();
+        let _p = self.props.reify_greater_than(b, x, y, Reification::Full);
+        b
+    }
+
+    /// Declare constraint `x == y` to hold whenever binary variable `b` is set.
+    pub fn half_reify_equals(&mut self, b: VarIdBinary, x: impl View, y: impl View) {
+        let _p = self.props.reify_equals(b, x, y, Reification::Half);
+    }
+
+    /// Declare constraint `x != y` to hold whenever binary variable `b` is set.
+    pub fn half_reify_not_equals(&mut self, b: VarIdBinary, x: impl View, y: impl View) {
+        let _p = self.props.reify_not_equals(b, x, y, Reification::Half);
+    }
+
+    /// Declare constraint `x <= y` to hold whenever binary variable `b` is set.
+    pub fn half_reify_less_than_or_equals(&mut self, b: VarIdBinary, x: impl View, y: impl View) {
+        let _p = self
+            .props
+            .reify_less_than_or_equals(b, x, y, Reification::Half);
+    }
+
+    /// Declare constraint `x < y` to hold whenever binary variable `b` is set.
+    pub fn half_reify_less_than(&mut self, b: VarIdBinary, x: impl View, y: impl View) {
+        let _p = self.props.reify_less_than(b, x, y, Reification::Half);
+    }
+
+    /// Declare constraint `x >= y` to hold whenever binary variable `b` is set.
+    pub fn half_reify_greater_than_or_equals(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+    ) {
+        let _p = self
+            .props
+            .reify_greater_than_or_equals(b, x, y, Reification::Half);
+    }
+
+    /// Declare constraint `x > y` to hold whenever binary variable `b` is set.
+    pub fn half_reify_greater_than(&mut self, b: VarIdBinary, x: impl View, y: impl View) {
+        let _p = self.props.reify_greater_than(b, x, y, Reification::Half);
+    }
+
+    /// Pick the strategy used to select which decision variable to branch on during search.
+    ///
+    /// Defaults to [`VariableSelection::InputOrder`].
+    /// It does not apply to variables declared in a group with [`Self::branch_on`].
+    pub fn set_variable_selection(&mut self, selection: VariableSelection) {
+        self.config.branching.set_variable_selection(selection);
+    }
+
+    /// Pick the strategy used to select which value to explore first when branching.
+    ///
+    /// Defaults to [`ValueSelection::SplitLower`].
+    /// It does not apply to variables declared in a group with [`Self::branch_on`].
+    pub fn set_value_selection(&mut self, selection: ValueSelection) {
+        self.config.branching.set_value_selection(selection);
+    }
+
+    /// Declare a group of decision variables to branch on with dedicated strategies.
+    ///
+    /// Groups are branched on in declaration order: search only moves on to the next group
+    /// once all variables of the previous ones are assigned.
+    /// Remaining variables are branched on last, with the default strategies.
+    pub fn branch_on(
+        &mut self,
+        xs: &[impl View],
+        variable_selection: VariableSelection,
+        value_selection: ValueSelection,
+    ) {
+        let vars = xs.iter().filter_map(|x| x.get_underlying_var()).collect();
+
+        self.config
+            .branching
+            .push_group(vars, variable_selection, value_selection);
+    }
+
+    /// Declare a custom brancher, chained after previously declared groups and branchers.
+    ///
+    /// Remaining variables are branched on last, with the default strategies.
+    pub fn branch_with(&mut self, brancher: impl Brancher + 'static) {
+        self.config.branching.push_brancher(Arc::new(brancher));
+    }
+
+    /// Set budget after which search stops, even if the search tree was not fully explored.
+    ///
+    /// Whether search completed can be checked with [`Solutions::status`].
+    pub fn set_limits(&mut self, limits: SearchLimits) {
+        self.config.limits = limits;
+    }
+
+    /// Restart search from the root whenever a run reaches the failure limit set by the schedule.
+    ///
+    /// Restarts pair well with randomized or failure-weighted branching strategies, which
+    /// explore a different part of the search tree after each restart.
+    pub fn set_restarts(&mut self, schedule: RestartSchedule) {
+        self.config.restarts_opt = Some(schedule);
+    }
+
+    /// Search with several threads, which share nodes to explore whenever one of them runs out.
+    ///
+    /// Threads share the best objective value found so far when optimizing, and search limits
+    /// apply to all of them together. Restart schedules are ignored by parallel search.
+    pub fn set_threads(&mut self, n: usize) {
+        self.config.threads = n;
+    }
+
     /// Find assignment that minimizes objective expression while satisfying all constraints.
     #[must_use]
     pub fn minimize(self, objective: impl View) -> Option<Solution> {
         self.minimize_and_iterate(objective).last()
     }
 
+    /// Find assignment that minimizes objective expression, and report on how search went.
+    #[must_use]
+    pub fn minimize_and_report(self, objective: impl View) -> Report {
+        let mut solutions = self.minimize_and_iterate(objective);
+        let solution = solutions.by_ref().last();
+
+        solutions.into_report(solution)
+    }
+
     /// Enumerate assignments that satisfy all constraints, while minimizing objective expression.
     ///
     /// The order in which assignments are yielded is not stable.
-    pub fn minimize_and_iterate(self, objective: impl View) -> impl Iterator<Item = Solution> {
-        search(self.vars, self.props, mode::Minimize::new(objective))
+    pub fn minimize_and_iterate(self, objective: impl View) -> Solutions {
+        search(
+            self.vars,
+            self.props,
+            Box::new(mode::Minimize::new(objective)),
+            self.config,
+        )
+    }
+
+    /// Enumerate assignments while minimizing objective expression, until `token` is cancelled.
+    ///
+    /// Cancelled searches behave as if a limit had been reached, see [`Self::set_limits`].
+    pub fn minimize_and_iterate_with_token(
+        mut self,
+        objective: impl View,
+        token: CancellationToken,
+    ) -> Solutions {
+        self.config.cancellation_opt = Some(token);
+        self.minimize_and_iterate(objective)
     }
 
     /// Find assignment that maximizes objective expression while satisfying all constraints.
@@ -261,23 +757,144 @@ impl Model {
         self.minimize(objective.opposite())
     }
 
+    /// Find assignment that maximizes objective expression, and report on how search went.
+    #[must_use]
+    pub fn maximize_and_report(self, objective: impl View) -> Report {
+        self.minimize_and_report(objective.opposite())
+    }
+
     /// Enumerate assignments that satisfy all constraints, while maximizing objective expression.
     ///
     /// The order in which assignments are yielded is not stable.
-    pub fn maximize_and_iterate(self, objective: impl View) -> impl Iterator<Item = Solution> {
+    pub fn maximize_and_iterate(self, objective: impl View) -> Solutions {
         self.minimize_and_iterate(objective.opposite())
     }
 
+    /// Enumerate assignments while maximizing objective expression, until `token` is cancelled.
+    ///
+    /// Cancelled searches behave as if a limit had been reached, see [`Self::set_limits`].
+    pub fn maximize_and_iterate_with_token(
+        self,
+        objective: impl View,
+        token: CancellationToken,
+    ) -> Solutions {
+        self.minimize_and_iterate_with_token(objective.opposite(), token)
+    }
+
+    /// Improve on a first feasible assignment with large neighborhood search, to minimize objective.
+    ///
+    /// Search is incomplete: the reported solution is only proven optimal if a neighborhood
+    /// leaves all variables free, and gets explored within its failure limit.
+    #[must_use]
+    pub fn minimize_with_lns(mut self, objective: impl View, lns: &Lns) -> Report {
+        // Objective value is read back from solutions, through a dedicated variable
+        let min = objective.min_raw(&self.vars);
+        let max = objective.max_raw(&self.vars);
+        let o = self.new_var_unchecked(min, max);
+        self.equals(o, objective);
+
+        search::lns(&self.vars, &self.props, o, &self.config, lns)
+    }
+
+    /// Improve on a first feasible assignment with large neighborhood search, to maximize objective.
+    #[must_use]
+    pub fn maximize_with_lns(self, objective: impl View, lns: &Lns) -> Report {
+        self.minimize_with_lns(objective.opposite(), lns)
+    }
+
+    /// Minimize objective expression by running differently configured searches concurrently.
+    ///
+    /// Members are expected to be copies of the same model, which only differ in how search is
+    /// configured: branching strategies, restarts, limits. They share the best objective value
+    /// found so far, and search is over once one of them explores its whole search tree.
+    #[must_use]
+    pub fn minimize_portfolio(members: Vec<Self>, objective: impl View) -> Report {
+        let members = members
+            .into_iter()
+            .map(|m| (m.vars, m.props, m.config))
+            .collect();
+
+        let mut solutions = search::portfolio(members, &mode::Minimize::new(objective));
+        let solution = solutions.by_ref().last();
+
+        solutions.into_report(solution)
+    }
+
+    /// Maximize objective expression by running differently configured searches concurrently.
+    #[must_use]
+    pub fn maximize_portfolio(members: Vec<Self>, objective: impl View) -> Report {
+        Self::minimize_portfolio(members, objective.opposite())
+    }
+
+    /// Search for a feasible assignment by running differently configured searches concurrently.
+    #[must_use]
+    pub fn solve_portfolio(members: Vec<Self>) -> Report {
+        let members = members
+            .into_iter()
+            .map(|m| (m.vars, m.props, m.config))
+            .collect();
+
+        let mut solutions = search::portfolio(members, &mode::Enumerate);
+        let solution = solutions.next();
+
+        solutions.into_report(solution)
+    }
+
     /// Search for assignment that satisfies all constraints within bounds of decision variables.
     #[must_use]
     pub fn solve(self) -> Option<Solution> {
         self.enumerate().next()
     }
 
+    /// Search for assignment that satisfies all constraints, until `token` is cancelled.
+    ///
+    /// Cancelled searches behave as if a limit had been reached, see [`Self::set_limits`].
+    #[must_use]
+    pub fn solve_with_token(self, token: CancellationToken) -> Option<Solution> {
+        self.enumerate_with_token(token).next()
+    }
+
+    /// Search for assignment that satisfies all constraints, and report on how search went.
+    #[must_use]
+    pub fn solve_and_report(self) -> Report {
+        let mut solutions = self.enumerate();
+        let solution = solutions.next();
+
+        solutions.into_report(solution)
+    }
+
     /// Enumerate all assignments that satisfy all constraints.
     ///
     /// The order in which assignments are yielded is not stable.
-    pub fn enumerate(self) -> impl Iterator<Item = Solution> {
-        search(self.vars, self.props, mode::Enumerate)
+    pub fn enumerate(self) -> Solutions {
+        search(
+            self.vars,
+            self.props,
+            Box::new(mode::Enumerate),
+            self.config,
+        )
+    }
+
+    /// Enumerate assignments that satisfy all constraints, until `token` is cancelled.
+    ///
+    /// The token only applies to this search: it is not kept by the model, nor by its copies.
+    /// Cancelled searches behave as if a limit had been reached, see [`Self::set_limits`].
+    pub fn enumerate_with_token(mut self, token: CancellationToken) -> Solutions {
+        self.config.cancellation_opt = Some(token);
+        self.enumerate()
     }
 }
+
+/// Gather scheduling parameters into a list of tasks, ignoring extra entries.
+fn tasks<V: View>(starts: &[V], durations: &[i32], demands: &[i32]) -> Vec<Task<V>> {
+    starts
+        .iter()
+        .zip(durations)
+        .zip(demands)
+        .map(|((&start, &duration), &demand)| Task {
+            start,
+            duration,
+            demand,
+        })
+        .collect()
+}
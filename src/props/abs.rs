@@ -0,0 +1,55 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Absolute value of a view: `|x| == s`.
+#[derive(Clone, Copy, Debug)]
+pub struct Abs<V> {
+    x: V,
+    s: VarId,
+}
+
+impl<V> Abs<V> {
+    pub const fn new(x: V, s: VarId) -> Self {
+        Self { x, s }
+    }
+}
+
+impl<V: View> Prune for Abs<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let (x_min, x_max) = (self.x.min(ctx), self.x.max(ctx));
+
+        // Derive bounds of the absolute value from the sign of the view
+        let (min, max) = if x_min >= 0 {
+            (x_min, x_max)
+        } else if x_max <= 0 {
+            (x_max.saturating_neg(), x_min.saturating_neg())
+        } else {
+            (0, core::cmp::max(x_min.saturating_neg(), x_max))
+        };
+
+        let s_min = self.s.try_set_min(min, ctx)?;
+        let s_max = self.s.try_set_max(max, ctx)?;
+
+        // View must lie in `[-s_max, -s_min] U [s_min, s_max]`
+        let _min = self.x.try_set_min(-s_max, ctx)?;
+        let _max = self.x.try_set_max(s_max, ctx)?;
+
+        if self.x.min(ctx) > -s_min {
+            let _min = self.x.try_set_min(s_min, ctx)?;
+        }
+
+        if self.x.max(ctx) < s_min {
+            let _max = self.x.try_set_max(-s_min, ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for Abs<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        core::iter::once(self.s).chain(self.x.get_underlying_var())
+    }
+}
@@ -0,0 +1,495 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::graph::strongly_connected_components;
+use super::{Propagate, Prune};
+
+/// Enforce pairwise distinct values with Hall interval reasoning: `xs[i] != xs[j]`.
+///
+/// Bounds consistency follows López-Ortiz et al., "A fast and simple algorithm for bounds
+/// consistency of the alldifferent constraint" (IJCAI 2003), which runs in `O(n log n)`.
+#[derive(Clone, Debug)]
+pub struct AllDifferentBounds<V> {
+    xs: Vec<V>,
+}
+
+impl<V> AllDifferentBounds<V> {
+    pub const fn new(xs: Vec<V>) -> Self {
+        Self { xs }
+    }
+}
+
+impl<V: View> Prune for AllDifferentBounds<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let mut hall = HallIntervals::new(self.xs.iter().map(|x| (x.min(ctx), x.max(ctx))));
+
+        hall.filter_lower()?;
+        hall.filter_upper()?;
+
+        for (x, iv) in self.xs.iter().zip(hall.intervals) {
+            let _min = x.try_set_min(iv.min, ctx)?;
+            let _max = x.try_set_max(iv.max, ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for AllDifferentBounds<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().filter_map(|x| x.get_underlying_var())
+    }
+}
+
+/// Domain bounds of a single view, along with its rank in the sorted list of all bounds.
+#[derive(Clone, Copy, Debug, Default)]
+struct Interval {
+    min: i32,
+    max: i32,
+    min_rank: usize,
+    max_rank: usize,
+}
+
+/// Scratch data for a single run of the bounds consistency algorithm.
+struct HallIntervals {
+    intervals: Vec<Interval>,
+    min_sorted: Vec<usize>,
+    max_sorted: Vec<usize>,
+    bounds: Vec<i64>,
+    t: Vec<usize>,
+    d: Vec<i64>,
+    h: Vec<usize>,
+    nb: usize,
+}
+
+impl HallIntervals {
+    /// Sort intervals by bounds, and assign a rank to each distinct bound value.
+    fn new(bounds: impl Iterator<Item = (i32, i32)>) -> Self {
+        let intervals: Vec<_> = bounds
+            .map(|(min, max)| Interval {
+                min,
+                max,
+                ..Interval::default()
+            })
+            .collect();
+
+        let n = intervals.len();
+
+        let mut min_sorted: Vec<_> = (0..n).collect();
+        let mut max_sorted: Vec<_> = (0..n).collect();
+        min_sorted.sort_by_key(|&i| intervals[i].min);
+        max_sorted.sort_by_key(|&i| intervals[i].max);
+
+        let mut hall = Self {
+            intervals,
+            min_sorted,
+            max_sorted,
+            bounds: vec![0; 2 * n + 2],
+            t: vec![0; 2 * n + 2],
+            d: vec![0; 2 * n + 2],
+            h: vec![0; 2 * n + 2],
+            nb: 0,
+        };
+
+        if n > 0 {
+            hall.rank_bounds();
+        }
+
+        hall
+    }
+
+    /// Merge sorted minimums and maximums (exclusive) into a single list of distinct bounds.
+    fn rank_bounds(&mut self) {
+        let n = self.intervals.len();
+
+        let mut min = i64::from(self.intervals[self.min_sorted[0]].min);
+        let mut max = i64::from(self.intervals[self.max_sorted[0]].max) + 1;
+        let mut last = min - 2;
+        let mut nb = 0;
+
+        self.bounds[0] = last;
+
+        let (mut i, mut j) = (0, 0);
+
+        loop {
+            if i < n && min <= max {
+                if min != last {
+                    nb += 1;
+                    last = min;
+                    self.bounds[nb] = min;
+                }
+
+                self.intervals[self.min_sorted[i]].min_rank = nb;
+
+                i += 1;
+
+                if i < n {
+                    min = i64::from(self.intervals[self.min_sorted[i]].min);
+                }
+            } else {
+                if max != last {
+                    nb += 1;
+                    last = max;
+                    self.bounds[nb] = max;
+                }
+
+                self.intervals[self.max_sorted[j]].max_rank = nb;
+
+                j += 1;
+
+                if j == n {
+                    break;
+                }
+
+                max = i64::from(self.intervals[self.max_sorted[j]].max) + 1;
+            }
+        }
+
+        self.nb = nb;
+        self.bounds[nb + 1] = self.bounds[nb] + 2;
+    }
+
+    /// Raise minimums of intervals that intersect a Hall interval from below.
+    #[allow(clippy::many_single_char_names)] // Names follow the paper
+    fn filter_lower(&mut self) -> Option<()> {
+        let (t, d, h, bounds) = (&mut self.t, &mut self.d, &mut self.h, &self.bounds);
+
+        for i in 1..=self.nb + 1 {
+            t[i] = i - 1;
+            h[i] = i - 1;
+            d[i] = bounds[i] - bounds[i - 1];
+        }
+
+        for &k in &self.max_sorted {
+            let iv = &mut self.intervals[k];
+            let (x, y) = (iv.min_rank, iv.max_rank);
+
+            let mut z = path_max(t, x + 1);
+            let j = t[z];
+
+            d[z] -= 1;
+
+            if d[z] == 0 {
+                t[z] = z + 1;
+                z = path_max(t, t[z]);
+                t[z] = j;
+            }
+
+            path_set(t, x + 1, z, z);
+
+            // Not enough values left for the intervals that end before this one: fail space
+            if d[z] < bounds[z] - bounds[y] {
+                return None;
+            }
+
+            if h[x] > x {
+                let w = path_max(h, h[x]);
+                iv.min = to_i32(bounds[w]);
+                path_set(h, x, w, w);
+            }
+
+            if d[z] == bounds[z] - bounds[y] {
+                let start = h[y];
+                path_set(h, start, j - 1, y);
+                h[y] = j - 1;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Lower maximums of intervals that intersect a Hall interval from above.
+    #[allow(clippy::many_single_char_names)] // Names follow the paper
+    fn filter_upper(&mut self) -> Option<()> {
+        let (t, d, h, bounds) = (&mut self.t, &mut self.d, &mut self.h, &self.bounds);
+
+        for i in 0..=self.nb {
+            t[i] = i + 1;
+            h[i] = i + 1;
+            d[i] = bounds[i + 1] - bounds[i];
+        }
+
+        for &k in self.min_sorted.iter().rev() {
+            let iv = &mut self.intervals[k];
+            let (x, y) = (iv.max_rank, iv.min_rank);
+
+            let mut z = path_min(t, x - 1);
+            let j = t[z];
+
+            d[z] -= 1;
+
+            if d[z] == 0 {
+                t[z] = z - 1;
+                z = path_min(t, t[z]);
+                t[z] = j;
+            }
+
+            path_set(t, x - 1, z, z);
+
+            // Not enough values left for the intervals that start after this one: fail space
+            if d[z] < bounds[y] - bounds[z] {
+                return None;
+            }
+
+            if h[x] < x {
+                let w = path_min(h, h[x]);
+                iv.max = to_i32(bounds[w] - 1);
+                path_set(h, x, w, w);
+            }
+
+            if d[z] == bounds[y] - bounds[z] {
+                let start = h[y];
+                path_set(h, start, j + 1, y);
+                h[y] = j + 1;
+            }
+        }
+
+        Some(())
+    }
+}
+
+/// Follow pointers of the provided tree while they lead to larger indices.
+fn path_max(t: &[usize], mut i: usize) -> usize {
+    while t[i] > i {
+        i = t[i];
+    }
+
+    i
+}
+
+/// Follow pointers of the provided tree while they lead to smaller indices.
+fn path_min(t: &[usize], mut i: usize) -> usize {
+    while t[i] < i {
+        i = t[i];
+    }
+
+    i
+}
+
+/// Compress path from `start` to `end`, making every node on it point to `to`.
+fn path_set(t: &mut [usize], start: usize, end: usize, to: usize) {
+    let mut l = start;
+
+    while l != end {
+        let k = l;
+        l = t[k];
+        t[k] = to;
+    }
+}
+
+/// Narrow bound back to the domain of decision variables, where it is guaranteed to fit.
+fn to_i32(bound: i64) -> i32 {
+    i32::try_from(bound).expect("bound derived from an existing domain")
+}
+
+/// Enforce pairwise distinct values with a maximum matching: `xs[i] != xs[j]`.
+///
+/// Domain consistency follows Régin, "A filtering algorithm for constraints of difference in
+/// CSPs" (AAAI 1994): values that do not belong to any maximum matching of the variable-value
+/// graph cannot be part of a solution. Its cost grows with the number of values in domains.
+#[derive(Clone, Debug)]
+pub struct AllDifferentDomain<V> {
+    xs: Vec<V>,
+    matching: Vec<Option<i32>>,
+}
+
+impl<V> AllDifferentDomain<V> {
+    pub fn new(xs: Vec<V>) -> Self {
+        let matching = vec![None; xs.len()];
+        Self { xs, matching }
+    }
+}
+
+impl<V: View> Prune for AllDifferentDomain<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Enumerate domain values, which are the edges of the variable-value graph
+        let domains: Vec<Vec<_>> = self
+            .xs
+            .iter()
+            .map(|x| x.iter_values(ctx).collect())
+            .collect();
+
+        let mut graph = ValueGraph::new(&domains, &self.matching);
+
+        // A variable that cannot be matched to a distinct value makes the space infeasible
+        for x in 0..domains.len() {
+            if graph.var_to_val[x].is_none() && !graph.augment(x) {
+                return None;
+            }
+        }
+
+        // Matching is stored in propagator state, to speed up the next run
+        self.matching = graph.matching();
+
+        let support = graph.find_support();
+
+        for (k, (x, edges)) in self.xs.iter().zip(&graph.edges).enumerate() {
+            for &i in edges {
+                if !support.is_supported(k, i) {
+                    x.try_remove_value(graph.values[i], ctx)?;
+                }
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for AllDifferentDomain<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().filter_map(|x| x.get_underlying_var())
+    }
+}
+
+/// Bipartite graph between variables and the union of their domains.
+struct ValueGraph {
+    /// Union of domains, sorted, which maps value vertices to actual values.
+    values: Vec<i32>,
+
+    /// Indices of values in the domain of each variable.
+    edges: Vec<Vec<usize>>,
+
+    var_to_val: Vec<Option<usize>>,
+    val_to_var: Vec<Option<usize>>,
+}
+
+impl ValueGraph {
+    /// Build graph, and reuse assignments of a previous matching that are still valid.
+    fn new(domains: &[Vec<i32>], previous: &[Option<i32>]) -> Self {
+        // Values are indexed densely, so that sparse domains do not inflate the graph
+        let mut values: Vec<_> = domains.iter().flatten().copied().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        let index = |value: &i32| values.binary_search(value).ok();
+
+        let edges: Vec<Vec<_>> = domains
+            .iter()
+            .map(|d| d.iter().filter_map(index).collect())
+            .collect();
+
+        let mut var_to_val = vec![None; domains.len()];
+        let mut val_to_var = vec![None; values.len()];
+
+        for (x, (values, value_opt)) in domains.iter().zip(previous).enumerate() {
+            if let Some(value) = value_opt.filter(|v| values.binary_search(v).is_ok()) {
+                if let Some(i) = index(&value).filter(|&i| val_to_var[i].is_none()) {
+                    var_to_val[x] = Some(i);
+                    val_to_var[i] = Some(x);
+                }
+            }
+        }
+
+        Self {
+            values,
+            edges,
+            var_to_val,
+            val_to_var,
+        }
+    }
+
+    /// Try to find an augmenting path that matches variable `x`, without unmatching others.
+    fn augment(&mut self, x: usize) -> bool {
+        let mut visited = vec![false; self.val_to_var.len()];
+        self.augment_rec(x, &mut visited)
+    }
+
+    /// Explore alternating paths depth-first, skipping values already visited by this search.
+    fn augment_rec(&mut self, x: usize, visited: &mut [bool]) -> bool {
+        // Edges are read by position, which leaves the matching free to change during iteration
+        for k in 0..self.edges[x].len() {
+            let i = self.edges[x][k];
+
+            if visited[i] {
+                continue;
+            }
+
+            visited[i] = true;
+
+            let is_available = match self.val_to_var[i] {
+                None => true,
+                Some(y) => self.augment_rec(y, visited),
+            };
+
+            if is_available {
+                self.var_to_val[x] = Some(i);
+                self.val_to_var[i] = Some(x);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Export matching as values, to remain valid if the union of domains changes.
+    fn matching(&self) -> Vec<Option<i32>> {
+        self.var_to_val
+            .iter()
+            .map(|i_opt| i_opt.map(|i| self.values[i]))
+            .collect()
+    }
+
+    /// Determine which edges of the graph belong to at least one maximum matching.
+    ///
+    /// Matched edges are oriented from variables to values, other edges from values to variables.
+    /// An edge is supported if it is matched, if it lies on an alternating cycle (both ends are
+    /// in the same strongly connected component), or if its value can be reached from a free value.
+    fn find_support(&self) -> Support {
+        let n_vars = self.var_to_val.len();
+        let n_nodes = n_vars + self.val_to_var.len();
+
+        // Vertices for variables come first, followed by vertices for values
+        let mut adjacency = vec![Vec::new(); n_nodes];
+
+        for (x, edges) in self.edges.iter().enumerate() {
+            for &i in edges {
+                if self.var_to_val[x] == Some(i) {
+                    adjacency[x].push(n_vars + i);
+                } else {
+                    adjacency[n_vars + i].push(x);
+                }
+            }
+        }
+
+        // Explore alternating paths that start from free values
+        let mut stack: Vec<_> = (0..self.val_to_var.len())
+            .filter(|&i| self.val_to_var[i].is_none())
+            .map(|i| n_vars + i)
+            .collect();
+
+        let mut is_reachable = vec![false; n_nodes];
+
+        while let Some(node) = stack.pop() {
+            if !is_reachable[node] {
+                is_reachable[node] = true;
+                stack.extend(adjacency[node].iter().filter(|&&m| !is_reachable[m]));
+            }
+        }
+
+        Support {
+            n_vars,
+            var_to_val: self.var_to_val.clone(),
+            is_reachable,
+            components: strongly_connected_components(&adjacency),
+        }
+    }
+}
+
+/// Summary of which variable-value pairs can be extended to a maximum matching.
+struct Support {
+    n_vars: usize,
+    var_to_val: Vec<Option<usize>>,
+    is_reachable: Vec<bool>,
+    components: Vec<usize>,
+}
+
+impl Support {
+    /// Determine if variable `x` can take the value at index `i` of the union of domains.
+    fn is_supported(&self, x: usize, i: usize) -> bool {
+        let node = self.n_vars + i;
+
+        self.var_to_val[x] == Some(i)
+            || self.is_reachable[node]
+            || self.components[x] == self.components[node]
+    }
+}
@@ -0,0 +1,256 @@
+use crate::vars::VarId;
+use crate::views::Context;
+
+use super::graph::strongly_connected_components;
+use super::{Propagate, Prune};
+
+/// Require successors to form a single cycle that visits every node: `i -> xs[i]`.
+///
+/// Subtours are eliminated along chains of fixed arcs, following Caseau and Laburthe,
+/// "Solving Small TSPs with Constraints" (ICLP 1997). The graph of remaining arcs must also be
+/// strongly connected, as in Kaya and Hooker, "A Filter for the Circuit Constraint" (CP 2006).
+#[derive(Clone, Debug)]
+pub struct Circuit {
+    xs: Vec<VarId>,
+}
+
+impl Circuit {
+    pub const fn new(xs: Vec<VarId>) -> Self {
+        Self { xs }
+    }
+}
+
+impl Prune for Circuit {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Node that loops on itself is left out, unless it is the only one
+        if self.xs.len() > 1 {
+            for (i, &x) in self.xs.iter().enumerate() {
+                ctx.try_remove_value(x, i32::try_from(i).ok()?)?;
+            }
+        }
+
+        prune_subcircuit(&self.xs, ctx)
+    }
+}
+
+impl Propagate for Circuit {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().copied()
+    }
+}
+
+/// Require successors to form a single cycle, except for nodes that loop on themselves.
+///
+/// Filtering is the one of [`Circuit`], where nodes that can loop on themselves are optional.
+/// Nodes that cannot reach all mandatory ones through a cycle are left out of it.
+#[derive(Clone, Debug)]
+pub struct Subcircuit {
+    xs: Vec<VarId>,
+}
+
+impl Subcircuit {
+    pub const fn new(xs: Vec<VarId>) -> Self {
+        Self { xs }
+    }
+}
+
+impl Prune for Subcircuit {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        prune_subcircuit(&self.xs, ctx)
+    }
+}
+
+impl Propagate for Subcircuit {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().copied()
+    }
+}
+
+/// Filter successors of a subcircuit, where mandatory nodes are those that cannot loop.
+fn prune_subcircuit(xs: &[VarId], ctx: &mut Context) -> Option<()> {
+    let n = i32::try_from(xs.len()).ok()?;
+
+    // Successors are indices of nodes
+    for &x in xs {
+        let _min = ctx.try_set_min(x, 0)?;
+        let _max = ctx.try_set_max(x, n - 1)?;
+    }
+
+    eliminate_subtours(xs, ctx)?;
+    connect_components(xs, ctx)
+}
+
+/// Forbid arcs that would close a chain of fixed arcs, before it visits all mandatory nodes.
+fn eliminate_subtours(xs: &[VarId], ctx: &mut Context) -> Option<()> {
+    let n = xs.len();
+    let nodes = Nodes::new(xs, ctx)?;
+
+    // Fixed successor of each node, other than itself
+    let fixed: Vec<_> = nodes
+        .successors
+        .iter()
+        .zip(&nodes.is_mandatory)
+        .map(|(s, &is_mandatory)| match s[..] {
+            [j] if is_mandatory => Some(j),
+            _ => None,
+        })
+        .collect();
+
+    let mut has_fixed_predecessor = vec![false; n];
+    for &j in fixed.iter().flatten() {
+        has_fixed_predecessor[j] = true;
+    }
+
+    let mut is_visited = vec![false; n];
+
+    for head in 0..n {
+        if fixed[head].is_none() || has_fixed_predecessor[head] {
+            continue;
+        }
+
+        // Follow the chain until its last node, which is still free to choose its successor
+        let mut chain = vec![head];
+        is_visited[head] = true;
+
+        let mut last = head;
+        let mut is_merged = false;
+
+        while let Some(next) = fixed[last] {
+            // Chains that merge are already ruled out by the components
+            if is_visited[next] {
+                is_merged = true;
+                break;
+            }
+
+            chain.push(next);
+            is_visited[next] = true;
+            last = next;
+        }
+
+        if !is_merged && nodes.has_mandatory_outside(&chain) {
+            ctx.try_remove_value(xs[last], i32::try_from(head).ok()?)?;
+        }
+    }
+
+    // Remaining fixed arcs form cycles, which leave no room for other mandatory nodes
+    for start in 0..n {
+        if fixed[start].is_none() || is_visited[start] {
+            continue;
+        }
+
+        let mut cycle = vec![start];
+        is_visited[start] = true;
+
+        let mut last = start;
+        while let Some(next) = fixed[last].filter(|&j| !is_visited[j]) {
+            cycle.push(next);
+            is_visited[next] = true;
+            last = next;
+        }
+
+        if nodes.has_mandatory_outside(&cycle) {
+            return None;
+        }
+
+        for (i, &x) in xs.iter().enumerate() {
+            if !cycle.contains(&i) {
+                let i = i32::try_from(i).ok()?;
+
+                let _min = ctx.try_set_min(x, i)?;
+                let _max = ctx.try_set_max(x, i)?;
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// Remove arcs between strongly connected components, which no cycle can use.
+///
+/// Mandatory nodes must all belong to the same component, and nodes outside of it are left out.
+fn connect_components(xs: &[VarId], ctx: &mut Context) -> Option<()> {
+    let nodes = Nodes::new(xs, ctx)?;
+    let components = strongly_connected_components(&nodes.successors);
+
+    let mut main = None;
+
+    for (i, &is_mandatory) in nodes.is_mandatory.iter().enumerate() {
+        if is_mandatory {
+            match main {
+                Some(c) if c != components[i] => return None,
+                _ => main = Some(components[i]),
+            }
+        }
+    }
+
+    let mut sizes = vec![0_usize; xs.len()];
+    for &c in &components {
+        sizes[c] += 1;
+    }
+
+    for (i, &x) in xs.iter().enumerate() {
+        let c = components[i];
+
+        // Node cannot be part of any cycle with the mandatory nodes, or of any cycle at all
+        if main.is_some_and(|m| m != c) || sizes[c] == 1 {
+            let i = i32::try_from(i).ok()?;
+
+            let _min = ctx.try_set_min(x, i)?;
+            let _max = ctx.try_set_max(x, i)?;
+
+            continue;
+        }
+
+        for &j in &nodes.successors[i] {
+            if components[j] != c {
+                ctx.try_remove_value(x, i32::try_from(j).ok()?)?;
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// Arcs of the graph defined by successor domains, ignoring nodes that loop on themselves.
+struct Nodes {
+    successors: Vec<Vec<usize>>,
+    is_mandatory: Vec<bool>,
+}
+
+impl Nodes {
+    fn new(xs: &[VarId], ctx: &Context) -> Option<Self> {
+        let mut successors = Vec::with_capacity(xs.len());
+        let mut is_mandatory = Vec::with_capacity(xs.len());
+
+        for (i, &x) in xs.iter().enumerate() {
+            let mut s = Vec::new();
+            let mut can_loop = false;
+
+            for value in ctx.iter_values(x) {
+                let j = usize::try_from(value).ok()?;
+
+                if j == i {
+                    can_loop = true;
+                } else {
+                    s.push(j);
+                }
+            }
+
+            successors.push(s);
+            is_mandatory.push(!can_loop);
+        }
+
+        Some(Self {
+            successors,
+            is_mandatory,
+        })
+    }
+
+    /// Determine if some mandatory node is not part of the provided list.
+    fn has_mandatory_outside(&self, nodes: &[usize]) -> bool {
+        self.is_mandatory
+            .iter()
+            .enumerate()
+            .any(|(i, &is_mandatory)| is_mandatory && !nodes.contains(&i))
+    }
+}
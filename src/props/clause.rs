@@ -0,0 +1,112 @@
+use crate::vars::{VarId, VarIdBinary};
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Disjunction of binary literals: `l_1 || l_2 || ... || l_n`.
+///
+/// Each literal is a binary variable with a polarity: `(x, true)` holds when `x` is set,
+/// `(x, false)` holds when `x` is unset. Two watched literals that can still hold are tracked:
+/// as long as neither of them is falsified, pruning does not need to scan the whole clause.
+/// Only the variables of watched literals schedule the propagator.
+#[derive(Clone, Debug)]
+pub struct Clause {
+    literals: Vec<(VarIdBinary, bool)>,
+    watches: [usize; 2],
+}
+
+impl Clause {
+    pub fn new(literals: Vec<(VarIdBinary, bool)>) -> Self {
+        Self {
+            watches: [0, literals.len().saturating_sub(1)],
+            literals,
+        }
+    }
+
+    /// Assignment state of a literal: `Some(true)` if it holds, `Some(false)` if it cannot.
+    fn get_status(&self, i: usize, ctx: &Context) -> Option<bool> {
+        let (x, polarity) = self.literals[i];
+
+        if x.min(ctx) == x.max(ctx) {
+            Some((x.min(ctx) == 1) == polarity)
+        } else {
+            None
+        }
+    }
+
+    /// Assign the variable of a literal so that it holds, failing the space if it cannot.
+    fn force(&self, i: usize, ctx: &mut Context) -> Option<()> {
+        let (x, polarity) = self.literals[i];
+
+        if polarity {
+            let _min = x.try_set_min(1, ctx)?;
+        } else {
+            let _max = x.try_set_max(0, ctx)?;
+        }
+
+        Some(())
+    }
+
+    /// Get the variable of a literal.
+    fn get_var(&self, i: usize) -> VarId {
+        self.literals[i].0 .0
+    }
+
+    /// Move a watch to another literal that can still hold, if there is one.
+    fn try_move_watch(&mut self, w: usize, ctx: &mut Context) -> bool {
+        let n = self.literals.len();
+        let [current, other] = [self.watches[w], self.watches[1 - w]];
+
+        // Search resumes after the current watch, so that falsified literals are not scanned again
+        let replacement = (1..n)
+            .map(|k| (current + k) % n)
+            .find(|&i| i != other && self.get_status(i, ctx) != Some(false));
+
+        if let Some(i) = replacement {
+            self.watches[w] = i;
+            ctx.move_trigger_var(self.get_var(current), self.get_var(i));
+        }
+
+        replacement.is_some()
+    }
+}
+
+impl Prune for Clause {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Empty clause can never hold
+        if self.literals.is_empty() {
+            return None;
+        }
+
+        // Clause with a single literal, watched twice, is enforced right away
+        if self.literals.len() == 1 {
+            return self.force(0, ctx);
+        }
+
+        for w in 0..2 {
+            // Clause holds: nothing left to enforce
+            if self.get_status(self.watches[w], ctx) == Some(true) {
+                return Some(());
+            }
+        }
+
+        for w in 0..2 {
+            if self.get_status(self.watches[w], ctx) == Some(false) && !self.try_move_watch(w, ctx)
+            {
+                // Only the other watched literal can hold: it must do so
+                return self.force(self.watches[1 - w], ctx);
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl Propagate for Clause {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        // Clause with a single literal watches it twice, but needs to be scheduled only once
+        let n_watched = self.literals.len().min(2);
+
+        self.watches[..n_watched].iter().map(|&i| self.get_var(i))
+    }
+}
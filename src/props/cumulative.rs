@@ -0,0 +1,309 @@
+use core::cmp::{max, min, Reverse};
+
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{saturate, Propagate, Prune};
+
+/// Task of a scheduling constraint, which occupies a resource from its start time on.
+#[derive(Clone, Copy, Debug)]
+pub struct Task<V> {
+    pub start: V,
+    pub duration: i32,
+    pub demand: i32,
+}
+
+/// Limit resource usage of tasks at any time: `sum(demand[i] | start[i] <= t < end[i]) <= c`.
+///
+/// Time-tabling builds the profile of compulsory parts, where tasks run in all schedules,
+/// and moves other tasks away from the times where they would exceed the capacity.
+#[derive(Clone, Debug)]
+pub struct Cumulative<V> {
+    tasks: Vec<Task<V>>,
+    capacity: i32,
+}
+
+impl<V> Cumulative<V> {
+    pub const fn new(tasks: Vec<Task<V>>, capacity: i32) -> Self {
+        Self { tasks, capacity }
+    }
+}
+
+/// Time interval where compulsory parts use a constant amount of resource.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    start: i64,
+    end: i64,
+    height: i64,
+}
+
+impl<V: View> Prune for Cumulative<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let capacity = i64::from(self.capacity);
+
+        // Tasks that do not use the resource are ignored
+        let tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|t| t.duration > 0 && t.demand > 0)
+            .collect();
+
+        // Resource usage changes at the bounds of compulsory parts
+        let mut events = Vec::new();
+
+        for task in &tasks {
+            let demand = i64::from(task.demand);
+
+            if demand > capacity {
+                return None;
+            }
+
+            let lst = i64::from(task.start.max(ctx));
+            let ect = i64::from(task.start.min(ctx)) + i64::from(task.duration);
+
+            if lst < ect {
+                events.push((lst, demand));
+                events.push((ect, -demand));
+            }
+        }
+
+        events.sort_unstable();
+
+        let mut profile = Vec::new();
+        let mut height = 0;
+
+        for (k, &(time, delta)) in events.iter().enumerate() {
+            height += delta;
+
+            // Segment ends at the next event, once all events at the current time are processed
+            if let Some(&(next, _)) = events.get(k + 1) {
+                if next > time && height > 0 {
+                    if height > capacity {
+                        return None;
+                    }
+
+                    profile.push(Segment {
+                        start: time,
+                        end: next,
+                        height,
+                    });
+                }
+            }
+        }
+
+        for task in &tasks {
+            let duration = i64::from(task.duration);
+            let demand = i64::from(task.demand);
+
+            // Compulsory part of the task is already accounted for in the profile
+            let lst = i64::from(task.start.max(ctx));
+            let ect = i64::from(task.start.min(ctx)) + duration;
+
+            let is_conflict = |segment: &Segment| {
+                let own = if lst <= segment.start && segment.end <= ect {
+                    demand
+                } else {
+                    0
+                };
+
+                segment.height - own + demand > capacity
+            };
+
+            // Push start past segments that leave too little capacity for the task
+            let mut est = i64::from(task.start.min(ctx));
+
+            for segment in &profile {
+                if segment.end <= est {
+                    continue;
+                }
+
+                if segment.start >= est + duration {
+                    break;
+                }
+
+                if is_conflict(segment) {
+                    est = segment.end;
+                }
+            }
+
+            let _min = task.start.try_set_min(saturate(est), ctx)?;
+
+            // Symmetrically, pull end before segments that leave too little capacity
+            let mut lct = i64::from(task.start.max(ctx)) + duration;
+
+            for segment in profile.iter().rev() {
+                if segment.start >= lct {
+                    continue;
+                }
+
+                if segment.end <= lct - duration {
+                    break;
+                }
+
+                if is_conflict(segment) {
+                    lct = segment.start;
+                }
+            }
+
+            let _max = task.start.try_set_max(saturate(lct - duration), ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for Cumulative<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.tasks
+            .iter()
+            .filter_map(|t| t.start.get_underlying_var())
+    }
+}
+
+/// Detect tasks that must end after a set of other tasks, given the energy they all require.
+///
+/// Edge-finding follows the rule of Mercier and Van Hentenryck, "Edge Finding for Cumulative
+/// Scheduling" (INFORMS Journal on Computing, 2008), restricted to task intervals that share
+/// their latest completion time, in `O(n^3)`. It is meant to complement time-tabling.
+#[derive(Clone, Debug)]
+pub struct CumulativeEdgeFinding<V> {
+    tasks: Vec<Task<V>>,
+    capacity: i32,
+}
+
+impl<V> CumulativeEdgeFinding<V> {
+    pub const fn new(tasks: Vec<Task<V>>, capacity: i32) -> Self {
+        Self { tasks, capacity }
+    }
+}
+
+/// Time window of a task, along with its resource requirements.
+#[derive(Clone, Copy, Debug)]
+struct Window {
+    est: i128,
+    lct: i128,
+    demand: i128,
+    energy: i128,
+}
+
+impl<V: View> Prune for CumulativeEdgeFinding<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let capacity = i128::from(self.capacity);
+
+        // Tasks that do not use the resource are ignored
+        let tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|t| t.duration > 0 && t.demand > 0)
+            .collect();
+
+        let windows: Vec<_> = tasks
+            .iter()
+            .map(|t| Window {
+                est: t.start.min(ctx).into(),
+                lct: i128::from(t.start.max(ctx)) + i128::from(t.duration),
+                demand: t.demand.into(),
+                energy: i128::from(t.duration) * i128::from(t.demand),
+            })
+            .collect();
+
+        // Earliest start times are raised, latest completion times are lowered on mirrored tasks
+        let ests = edge_finding(&windows, capacity)?;
+
+        let mirrored: Vec<_> = windows
+            .iter()
+            .map(|w| Window {
+                est: -w.lct,
+                lct: -w.est,
+                ..*w
+            })
+            .collect();
+
+        let lcts = edge_finding(&mirrored, capacity)?;
+
+        for ((task, est), lct) in tasks.iter().zip(ests).zip(lcts) {
+            let lst = -lct - i128::from(task.duration);
+
+            let _min = task.start.try_set_min(saturate_wide(est), ctx)?;
+            let _max = task.start.try_set_max(saturate_wide(lst), ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for CumulativeEdgeFinding<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.tasks
+            .iter()
+            .filter_map(|t| t.start.get_underlying_var())
+    }
+}
+
+/// Compute new earliest start times, or detect that some task interval is overloaded.
+fn edge_finding(windows: &[Window], capacity: i128) -> Option<Vec<i128>> {
+    let mut ests: Vec<_> = windows.iter().map(|w| w.est).collect();
+
+    let mut uppers: Vec<_> = windows.iter().map(|w| w.lct).collect();
+    uppers.sort_unstable();
+    uppers.dedup();
+
+    for upper in uppers {
+        // Tasks that complete by the upper bound, by decreasing earliest start time
+        let mut inside: Vec<_> = windows.iter().filter(|w| w.lct <= upper).collect();
+        inside.sort_unstable_by_key(|w| Reverse(w.est));
+
+        // Energy of task intervals `[lower, upper]`, as the lower bound decreases
+        let mut intervals = Vec::new();
+        let mut energy = 0;
+
+        for (k, w) in inside.iter().enumerate() {
+            energy += w.energy;
+
+            if inside.get(k + 1).map_or(true, |next| next.est < w.est) {
+                // Tasks of the interval need more energy than the resource can provide
+                if energy > capacity * (upper - w.est) {
+                    return None;
+                }
+
+                intervals.push((w.est, energy));
+            }
+        }
+
+        for (i, w) in windows.iter().enumerate() {
+            if w.lct <= upper {
+                continue;
+            }
+
+            // Largest interval the task cannot fit in, alongside the interval's own tasks
+            let detected = intervals.iter().rposition(|&(lower, energy)| {
+                capacity * (upper - min(lower, w.est)) < energy + w.energy
+            });
+
+            let Some(end) = detected else {
+                continue;
+            };
+
+            // Task starts once sub-intervals have used up the capacity it does not need
+            for &(lower, energy) in &intervals[..=end] {
+                let rest = energy - (capacity - w.demand) * (upper - lower);
+
+                if rest > 0 {
+                    ests[i] = max(ests[i], lower + div_ceil(rest, w.demand));
+                }
+            }
+        }
+    }
+
+    Some(ests)
+}
+
+/// Integer division rounding towards positive infinity, for a positive divisor.
+const fn div_ceil(a: i128, b: i128) -> i128 {
+    a.div_euclid(b) + if a.rem_euclid(b) > 0 { 1 } else { 0 }
+}
+
+/// Convert a wide integer to the closest `i32` value.
+fn saturate_wide(x: i128) -> i32 {
+    i32::try_from(x.clamp(i32::MIN.into(), i32::MAX.into())).unwrap_or_default()
+}
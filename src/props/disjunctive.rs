@@ -0,0 +1,404 @@
+use core::cmp::{max, min, Ordering, Reverse};
+
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{saturate, Propagate, Prune};
+
+/// Prevent tasks from overlapping on a unary resource: `end[i] <= start[j] || end[j] <= start[i]`.
+///
+/// Filtering follows Vilím, "O(n log n) Filtering Algorithms for Unary Resource Constraint"
+/// (CPAIOR 2004) and "Global Constraints in Scheduling" (PhD thesis, 2007): overload checking,
+/// detectable precedences, not-first/not-last and edge-finding, all based on Θ-trees.
+/// Each rule adjusts earliest start times, and runs on mirrored tasks for latest end times.
+#[derive(Clone, Debug)]
+pub struct Disjunctive<V> {
+    starts: Vec<V>,
+    durations: Vec<i32>,
+}
+
+impl<V> Disjunctive<V> {
+    pub const fn new(starts: Vec<V>, durations: Vec<i32>) -> Self {
+        Self { starts, durations }
+    }
+}
+
+impl<V: View> Prune for Disjunctive<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Tasks without duration never overlap with others
+        let active: Vec<_> = self
+            .starts
+            .iter()
+            .zip(&self.durations)
+            .filter(|&(_, &d)| d > 0)
+            .collect();
+
+        let tasks: Vec<_> = active
+            .iter()
+            .map(|&(&start, &duration)| Bounds {
+                est: start.min(ctx).into(),
+                lct: i64::from(start.max(ctx)) + i64::from(duration),
+                p: duration.into(),
+            })
+            .collect();
+
+        let mirrored: Vec<_> = tasks
+            .iter()
+            .map(|t| Bounds {
+                est: -t.lct,
+                lct: -t.est,
+                p: t.p,
+            })
+            .collect();
+
+        let ests = filter_lower(&tasks)?;
+        let lcts = filter_lower(&mirrored)?;
+
+        for ((&(&start, &duration), est), lct) in active.iter().zip(ests).zip(lcts) {
+            let _min = start.try_set_min(saturate(est), ctx)?;
+            let _max = start.try_set_max(saturate(-lct - i64::from(duration)), ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for Disjunctive<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.starts.iter().filter_map(|x| x.get_underlying_var())
+    }
+}
+
+/// Time window of a task, along with its processing time.
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    est: i64,
+    lct: i64,
+    p: i64,
+}
+
+impl Bounds {
+    const fn ect(self) -> i64 {
+        self.est + self.p
+    }
+
+    const fn lst(self) -> i64 {
+        self.lct - self.p
+    }
+}
+
+/// Combine all rules into new earliest start times, or detect that the resource is overloaded.
+fn filter_lower(tasks: &[Bounds]) -> Option<Vec<i64>> {
+    let mut ests: Vec<_> = tasks.iter().map(|t| t.est).collect();
+
+    let updates = [
+        detectable_precedences(tasks),
+        not_first(tasks),
+        edge_finding(tasks)?,
+    ];
+
+    for update in updates {
+        for (est, new) in ests.iter_mut().zip(update) {
+            *est = max(*est, new);
+        }
+    }
+
+    Some(ests)
+}
+
+/// Tasks that must start before `i` ends all precede it: `ect[i] > lst[j] => j << i`.
+fn detectable_precedences(tasks: &[Bounds]) -> Vec<i64> {
+    let mut tree = ThetaLambdaTree::new(tasks);
+    let mut ests: Vec<_> = tasks.iter().map(|t| t.est).collect();
+
+    let mut by_latest_start: Vec<_> = (0..tasks.len()).collect();
+    by_latest_start.sort_unstable_by_key(|&j| tasks[j].lst());
+    let mut queue = by_latest_start.into_iter().peekable();
+
+    let mut by_ect: Vec<_> = (0..tasks.len()).collect();
+    by_ect.sort_unstable_by_key(|&i| tasks[i].ect());
+
+    for i in by_ect {
+        while let Some(j) = queue.next_if(|&j| tasks[i].ect() > tasks[j].lst()) {
+            tree.insert(j);
+        }
+
+        // Task itself is not one of its own predecessors
+        let is_inserted = tree.contains(i);
+        if is_inserted {
+            tree.remove(i);
+        }
+
+        ests[i] = max(ests[i], tree.ect());
+
+        if is_inserted {
+            tree.insert(i);
+        }
+    }
+
+    ests
+}
+
+/// Task `i` cannot start first among a set that would not fit after it: `j` must end before.
+///
+/// Rule is the mirror of not-last, which is how Vilím describes it.
+fn not_first(tasks: &[Bounds]) -> Vec<i64> {
+    let mirrored: Vec<_> = tasks
+        .iter()
+        .map(|t| Bounds {
+            est: -t.lct,
+            lct: -t.est,
+            p: t.p,
+        })
+        .collect();
+
+    not_last(&mirrored).into_iter().map(|lct| -lct).collect()
+}
+
+/// Task `i` cannot end last among a set that would not fit before it: `lct[i] <= max(lst[j])`.
+fn not_last(tasks: &[Bounds]) -> Vec<i64> {
+    let mut tree = ThetaLambdaTree::new(tasks);
+    let mut lcts: Vec<_> = tasks.iter().map(|t| t.lct).collect();
+
+    let mut by_latest_start: Vec<_> = (0..tasks.len()).collect();
+    by_latest_start.sort_unstable_by_key(|&j| tasks[j].lst());
+    let mut queue = by_latest_start.into_iter().peekable();
+
+    let mut by_lct: Vec<_> = (0..tasks.len()).collect();
+    by_lct.sort_unstable_by_key(|&i| tasks[i].lct);
+
+    // Last two tasks inserted, which have the largest latest start times in the tree
+    let mut last = None;
+    let mut before_last = None;
+
+    for i in by_lct {
+        while let Some(j) = queue.next_if(|&j| tasks[i].lct > tasks[j].lst()) {
+            tree.insert(j);
+
+            before_last = last;
+            last = Some(j);
+        }
+
+        let is_inserted = tree.contains(i);
+        if is_inserted {
+            tree.remove(i);
+        }
+
+        // Other tasks that must start before `i` ends would not all fit before it starts
+        let j_opt = if last == Some(i) { before_last } else { last };
+
+        if let Some(j) = j_opt {
+            if tree.ect() > tasks[i].lst() {
+                lcts[i] = min(lcts[i], tasks[j].lst());
+            }
+        }
+
+        if is_inserted {
+            tree.insert(i);
+        }
+    }
+
+    lcts
+}
+
+/// Task `i` ends after a set of tasks, if they cannot all fit with it before their deadline.
+///
+/// Also detects overloaded sets of tasks, which cannot fit before their own deadline.
+fn edge_finding(tasks: &[Bounds]) -> Option<Vec<i64>> {
+    let mut tree = ThetaLambdaTree::new(tasks);
+    let mut ests: Vec<_> = tasks.iter().map(|t| t.est).collect();
+
+    for i in 0..tasks.len() {
+        tree.insert(i);
+    }
+
+    let mut by_lct: Vec<_> = (0..tasks.len()).collect();
+    by_lct.sort_unstable_by_key(|&j| Reverse(tasks[j].lct));
+
+    for (k, &j) in by_lct.iter().enumerate() {
+        // Tasks that must end by the deadline of `j` do not fit before it
+        if tree.ect() > tasks[j].lct {
+            return None;
+        }
+
+        tree.make_gray(j);
+
+        let Some(&next) = by_lct.get(k + 1) else {
+            break;
+        };
+
+        // Gray tasks that cannot fit alongside the remaining ones end after all of them
+        while tree.ect_gray() > tasks[next].lct {
+            let Some(i) = tree.get_responsible_gray() else {
+                break;
+            };
+
+            ests[i] = max(ests[i], tree.ect());
+            tree.remove(i);
+        }
+    }
+
+    Some(ests)
+}
+
+/// Earliest completion time of sets of tasks, with an optional gray task to add to the set.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    sum: i64,
+    ect: i64,
+    sum_gray: i64,
+    ect_gray: i64,
+
+    /// Gray task responsible for each gray value, if any.
+    sum_responsible: Option<usize>,
+    ect_responsible: Option<usize>,
+}
+
+impl Node {
+    /// Value that stands for minus infinity, with room to add processing times to it.
+    const NONE: i64 = i64::MIN / 4;
+
+    const EMPTY: Self = Self {
+        sum: 0,
+        ect: Self::NONE,
+        sum_gray: 0,
+        ect_gray: Self::NONE,
+        sum_responsible: None,
+        ect_responsible: None,
+    };
+
+    fn merge(left: &Self, right: &Self) -> Self {
+        let (sum_gray, sum_responsible) = max_responsible([
+            (left.sum_gray + right.sum, left.sum_responsible),
+            (left.sum + right.sum_gray, right.sum_responsible),
+        ]);
+
+        let (ect_gray, ect_responsible) = max_responsible([
+            (right.ect_gray, right.ect_responsible),
+            (left.ect + right.sum_gray, right.sum_responsible),
+            (left.ect_gray + right.sum, left.ect_responsible),
+        ]);
+
+        Self {
+            sum: left.sum + right.sum,
+            ect: max(right.ect, left.ect + right.sum),
+            sum_gray,
+            ect_gray,
+            sum_responsible,
+            ect_responsible,
+        }
+    }
+}
+
+/// Largest value, preferring values a gray task is responsible for on ties.
+fn max_responsible<const N: usize>(candidates: [(i64, Option<usize>); N]) -> (i64, Option<usize>) {
+    candidates
+        .into_iter()
+        .max_by(|a, b| match a.0.cmp(&b.0) {
+            Ordering::Equal => a.1.is_some().cmp(&b.1.is_some()),
+            ordering => ordering,
+        })
+        .unwrap_or((Node::NONE, None))
+}
+
+/// Balanced binary tree over tasks sorted by earliest start time, as in Vilím's Θ-Λ-tree.
+///
+/// Tasks are either absent, white (in Θ) or gray (in Λ).
+struct ThetaLambdaTree<'a> {
+    tasks: &'a [Bounds],
+
+    /// Leaf position of each task.
+    leaves: Vec<usize>,
+    nodes: Vec<Node>,
+    is_inserted: Vec<bool>,
+}
+
+impl<'a> ThetaLambdaTree<'a> {
+    fn new(tasks: &'a [Bounds]) -> Self {
+        let mut by_est: Vec<_> = (0..tasks.len()).collect();
+        by_est.sort_unstable_by_key(|&i| tasks[i].est);
+
+        let size = tasks.len().next_power_of_two();
+
+        let mut leaves = vec![0; tasks.len()];
+        for (rank, i) in by_est.into_iter().enumerate() {
+            leaves[i] = size + rank;
+        }
+
+        Self {
+            tasks,
+            leaves,
+            nodes: vec![Node::EMPTY; 2 * size],
+            is_inserted: vec![false; tasks.len()],
+        }
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.is_inserted[i]
+    }
+
+    fn ect(&self) -> i64 {
+        self.nodes.get(1).map_or(Node::NONE, |root| root.ect)
+    }
+
+    fn ect_gray(&self) -> i64 {
+        self.nodes.get(1).map_or(Node::NONE, |root| root.ect_gray)
+    }
+
+    fn get_responsible_gray(&self) -> Option<usize> {
+        self.nodes.get(1)?.ect_responsible
+    }
+
+    /// Add task to Θ.
+    fn insert(&mut self, i: usize) {
+        let t = self.tasks[i];
+
+        self.is_inserted[i] = true;
+        self.set_leaf(
+            i,
+            Node {
+                sum: t.p,
+                ect: t.ect(),
+                sum_gray: t.p,
+                ect_gray: t.ect(),
+                sum_responsible: None,
+                ect_responsible: None,
+            },
+        );
+    }
+
+    /// Move task from Θ to Λ.
+    fn make_gray(&mut self, i: usize) {
+        let t = self.tasks[i];
+
+        self.is_inserted[i] = false;
+        self.set_leaf(
+            i,
+            Node {
+                sum: 0,
+                ect: Node::NONE,
+                sum_gray: t.p,
+                ect_gray: t.ect(),
+                sum_responsible: Some(i),
+                ect_responsible: Some(i),
+            },
+        );
+    }
+
+    /// Remove task from both Θ and Λ.
+    fn remove(&mut self, i: usize) {
+        self.is_inserted[i] = false;
+        self.set_leaf(i, Node::EMPTY);
+    }
+
+    fn set_leaf(&mut self, i: usize, leaf: Node) {
+        let mut k = self.leaves[i];
+        self.nodes[k] = leaf;
+
+        // Refresh ancestors up to the root
+        while k > 1 {
+            k /= 2;
+            self.nodes[k] = Node::merge(&self.nodes[2 * k], &self.nodes[2 * k + 1]);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{saturate, Propagate, Prune};
+
+/// Divide a view by another, rounding towards zero like Rust's `/` operator: `x / y == s`.
+#[derive(Clone, Copy, Debug)]
+pub struct Div<U, V> {
+    x: U,
+    y: V,
+    s: VarId,
+}
+
+impl<U, V> Div<U, V> {
+    pub const fn new(x: U, y: V, s: VarId) -> Self {
+        Self { x, y, s }
+    }
+}
+
+impl<U: View, V: View> Prune for Div<U, V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Division by zero is undefined
+        self.y.try_remove_value(0, ctx)?;
+
+        let x = (i64::from(self.x.min(ctx)), i64::from(self.x.max(ctx)));
+        let (y_min, y_max) = (i64::from(self.y.min(ctx)), i64::from(self.y.max(ctx)));
+
+        // Split divisor on both sides of zero, to work on intervals of constant sign
+        let parts: Vec<_> = [(y_min, y_max.min(-1)), (y_min.max(1), y_max)]
+            .into_iter()
+            .filter(|&(min, max)| min <= max)
+            .collect();
+
+        // Quotient is monotonic in both operands when divisor sign is fixed: check corners
+        let (min, max) = hull(parts.iter().map(|&y| quotient_bounds(x, y)))?;
+
+        let _min = self.s.try_set_min(saturate(min), ctx)?;
+        let _max = self.s.try_set_max(saturate(max), ctx)?;
+
+        // Dividend lies between `s * y` and `s * y` plus a remainder smaller than the divisor
+        let s = (i64::from(self.s.min(ctx)), i64::from(self.s.max(ctx)));
+        let (min, max) = hull(parts.iter().map(|&y| dividend_bounds(s, y)))?;
+
+        let _min = self.x.try_set_min(saturate(min), ctx)?;
+        let _max = self.x.try_set_max(saturate(max), ctx)?;
+
+        // A non-zero quotient bounds the magnitude of the divisor by the magnitude of the dividend
+        let (s_min, s_max) = s;
+
+        if s_min > 0 || s_max < 0 {
+            let x_abs_max = i64::from(self.x.min(ctx))
+                .abs()
+                .max(i64::from(self.x.max(ctx)).abs());
+            let s_abs_min = s_min.abs().min(s_max.abs());
+            let y_abs_max = saturate(x_abs_max / s_abs_min);
+
+            let _min = self.y.try_set_min(-y_abs_max, ctx)?;
+            let _max = self.y.try_set_max(y_abs_max, ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<U: View, V: View> Propagate for Div<U, V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        core::iter::once(self.s)
+            .chain(self.x.get_underlying_var())
+            .chain(self.y.get_underlying_var())
+    }
+}
+
+/// Smallest interval that contains all the provided ones.
+fn hull(intervals: impl Iterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    intervals.reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+}
+
+/// Bounds of `x / y`, for an interval `y` that excludes zero.
+fn quotient_bounds((x_min, x_max): (i64, i64), (y_min, y_max): (i64, i64)) -> (i64, i64) {
+    let corners = [x_min / y_min, x_min / y_max, x_max / y_min, x_max / y_max];
+
+    let min = corners.into_iter().min().unwrap_or_default();
+    let max = corners.into_iter().max().unwrap_or_default();
+
+    (min, max)
+}
+
+/// Bounds of `x` such that `x / y` lies in `s`, for an interval `y` that excludes zero.
+fn dividend_bounds((s_min, s_max): (i64, i64), (y_min, y_max): (i64, i64)) -> (i64, i64) {
+    if y_min < 0 {
+        // Negating both operands leaves the quotient unchanged
+        let (min, max) = dividend_bounds((s_min, s_max), (-y_max, -y_min));
+        return (-max, -min);
+    }
+
+    // Largest dividend, with a positive remainder if quotient is non-negative
+    let max = if s_max >= 0 {
+        s_max * y_max + y_max - 1
+    } else {
+        s_max * y_min
+    };
+
+    // Smallest dividend, with a negative remainder if quotient is non-positive
+    let min = if s_min <= 0 {
+        s_min * y_max - (y_max - 1)
+    } else {
+        s_min * y_min
+    };
+
+    (min, max)
+}
@@ -0,0 +1,128 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Index a constant array with a decision variable: `array[index] == s`.
+#[derive(Clone, Debug)]
+pub struct Element {
+    index: VarId,
+    array: Vec<i32>,
+    s: VarId,
+}
+
+impl Element {
+    pub const fn new(index: VarId, array: Vec<i32>, s: VarId) -> Self {
+        Self { index, array, s }
+    }
+}
+
+impl Prune for Element {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let n = i32::try_from(self.array.len()).ok()?;
+
+        // Restrict index to valid positions of the array
+        let _min = self.index.try_set_min(0, ctx)?;
+        let _max = self.index.try_set_max(n - 1, ctx)?;
+
+        // Remove indices that point to values the expression can no longer take
+        let indices: Vec<_> = ctx.iter_values(self.index).collect();
+
+        let mut supported = Vec::with_capacity(indices.len());
+
+        for i in indices {
+            let value = self.array[usize::try_from(i).ok()?];
+
+            if self.s.contains(value, ctx) {
+                supported.push(value);
+            } else {
+                self.index.try_remove_value(i, ctx)?;
+            }
+        }
+
+        supported.sort_unstable();
+
+        // Remove values that are not reachable from any remaining index
+        let values: Vec<_> = ctx.iter_values(self.s).collect();
+
+        for value in values {
+            if supported.binary_search(&value).is_err() {
+                self.s.try_remove_value(value, ctx)?;
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl Propagate for Element {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        [self.index, self.s].into_iter()
+    }
+}
+
+/// Index an array of views with a decision variable: `array[index] == s`.
+#[derive(Clone, Debug)]
+pub struct ElementVar<V> {
+    index: VarId,
+    array: Vec<V>,
+    s: VarId,
+}
+
+impl<V> ElementVar<V> {
+    pub const fn new(index: VarId, array: Vec<V>, s: VarId) -> Self {
+        Self { index, array, s }
+    }
+}
+
+impl<V: View> Prune for ElementVar<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let n = i32::try_from(self.array.len()).ok()?;
+
+        // Restrict index to valid positions of the array
+        let _min = self.index.try_set_min(0, ctx)?;
+        let _max = self.index.try_set_max(n - 1, ctx)?;
+
+        // Remove indices that point to views whose bounds do not overlap with the expression
+        let indices: Vec<_> = ctx.iter_values(self.index).collect();
+
+        let mut min = i32::MAX;
+        let mut max = i32::MIN;
+
+        for i in indices {
+            let x = self.array[usize::try_from(i).ok()?];
+
+            if x.max(ctx) < self.s.min(ctx) || x.min(ctx) > self.s.max(ctx) {
+                self.index.try_remove_value(i, ctx)?;
+            } else {
+                min = core::cmp::min(min, x.min(ctx));
+                max = core::cmp::max(max, x.max(ctx));
+            }
+        }
+
+        // Expression is bounded by the views that remain reachable
+        let _min = self.s.try_set_min(min, ctx)?;
+        let _max = self.s.try_set_max(max, ctx)?;
+
+        // Once index is assigned, selected view and expression are equal
+        if self.index.min(ctx) == self.index.max(ctx) {
+            let x = self.array[usize::try_from(self.index.min(ctx)).ok()?];
+
+            let _min = x.try_set_min(self.s.min(ctx), ctx)?;
+            let _max = x.try_set_max(self.s.max(ctx), ctx)?;
+
+            let _min = self.s.try_set_min(x.min(ctx), ctx)?;
+            let _max = self.s.try_set_max(x.max(ctx), ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for ElementVar<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        [self.index, self.s]
+            .into_iter()
+            .chain(self.array.iter().filter_map(|x| x.get_underlying_var()))
+    }
+}
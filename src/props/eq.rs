@@ -1,6 +1,7 @@
 use crate::vars::VarId;
 use crate::views::{Context, View};
 
+use super::reif::Entail;
 use super::{Propagate, Prune};
 
 /// Enforce equality between two views: `x == y`.
@@ -36,3 +37,11 @@ impl<U: View, V: View> Propagate for Equals<U, V> {
             .chain(self.y.get_underlying_var())
     }
 }
+
+impl<U: View, V: View> Entail for Equals<U, V> {
+    fn is_entailed(&self, ctx: &Context) -> bool {
+        let (x, y) = (self.x, self.y);
+
+        x.min(ctx) == x.max(ctx) && y.min(ctx) == y.max(ctx) && x.min(ctx) == y.min(ctx)
+    }
+}
@@ -0,0 +1,70 @@
+/// Label each node with the index of its strongly connected component, using Tarjan's algorithm.
+///
+/// Recursion is replaced by an explicit stack of nodes along with their next arc to explore.
+pub fn strongly_connected_components(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let n = adjacency.len();
+
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut component = vec![usize::MAX; n];
+
+    let mut stack = Vec::new();
+    let mut n_visited = 0;
+    let mut n_components = 0;
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+
+        let mut calls = vec![(root, 0)];
+
+        while let Some(&(node, next)) = calls.last() {
+            if next == 0 {
+                index[node] = n_visited;
+                low[node] = n_visited;
+                n_visited += 1;
+
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&succ) = adjacency[node].get(next) {
+                if let Some(call) = calls.last_mut() {
+                    call.1 += 1;
+                }
+
+                if index[succ] == usize::MAX {
+                    calls.push((succ, 0));
+                } else if on_stack[succ] {
+                    low[node] = low[node].min(index[succ]);
+                }
+
+                continue;
+            }
+
+            let _call = calls.pop();
+
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+
+            // Node is the root of a component: pop all its members
+            if low[node] == index[node] {
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component[member] = n_components;
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                n_components += 1;
+            }
+        }
+    }
+
+    component
+}
@@ -1,6 +1,7 @@
 use crate::vars::VarId;
 use crate::views::{Context, View};
 
+use super::reif::Entail;
 use super::{Propagate, Prune};
 
 /// Enforce inequality between two views: `x <= y`.
@@ -33,3 +34,9 @@ impl<U: View, V: View> Propagate for LessThanOrEquals<U, V> {
             .chain(self.y.get_underlying_var())
     }
 }
+
+impl<U: View, V: View> Entail for LessThanOrEquals<U, V> {
+    fn is_entailed(&self, ctx: &Context) -> bool {
+        self.x.max(ctx) <= self.y.min(ctx)
+    }
+}
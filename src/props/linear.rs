@@ -0,0 +1,120 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{saturate, Propagate, Prune};
+
+/// Relational operator between the two sides of a linear constraint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// Left-hand side is lower than or equal to the right-hand side: `<=`.
+    Le,
+
+    /// Left-hand side is greater than or equal to the right-hand side: `>=`.
+    Ge,
+
+    /// Both sides are equal: `==`.
+    Eq,
+
+    /// Both sides differ: `!=`.
+    Ne,
+}
+
+/// Compare the sum of a list of views to a constant: `sum(x) <op> rhs`.
+///
+/// Coefficients are expected to be folded into the views beforehand, by scaling them.
+#[derive(Clone, Debug)]
+pub struct Linear<V> {
+    xs: Vec<V>,
+    relation: Relation,
+    rhs: i32,
+}
+
+impl<V> Linear<V> {
+    pub const fn new(xs: Vec<V>, relation: Relation, rhs: i32) -> Self {
+        Self { xs, relation, rhs }
+    }
+}
+
+impl<V: View> Linear<V> {
+    /// Bound the sum of terms from above: `sum(x) <= rhs`.
+    fn prune_max(&self, ctx: &mut Context) -> Option<()> {
+        let rhs = i64::from(self.rhs);
+        let min_of_terms: i64 = self.xs.iter().map(|x| i64::from(x.min(ctx))).sum();
+
+        if min_of_terms > rhs {
+            return None;
+        }
+
+        for x in &self.xs {
+            let max = rhs - (min_of_terms - i64::from(x.min(ctx)));
+            let _max = x.try_set_max(saturate(max), ctx)?;
+        }
+
+        Some(())
+    }
+
+    /// Bound the sum of terms from below: `sum(x) >= rhs`.
+    fn prune_min(&self, ctx: &mut Context) -> Option<()> {
+        let rhs = i64::from(self.rhs);
+        let max_of_terms: i64 = self.xs.iter().map(|x| i64::from(x.max(ctx))).sum();
+
+        if max_of_terms < rhs {
+            return None;
+        }
+
+        for x in &self.xs {
+            let min = rhs - (max_of_terms - i64::from(x.max(ctx)));
+            let _min = x.try_set_min(saturate(min), ctx)?;
+        }
+
+        Some(())
+    }
+
+    /// Forbid the last unassigned term from completing the sum to `rhs`.
+    fn prune_not_equals(&self, ctx: &mut Context) -> Option<()> {
+        let mut unassigned = self.xs.iter().filter(|x| x.min(ctx) != x.max(ctx));
+
+        // Wait until at most one term is left before doing anything
+        let last = unassigned.next();
+        if unassigned.next().is_some() {
+            return Some(());
+        }
+
+        let sum_of_assigned: i64 = self
+            .xs
+            .iter()
+            .filter(|x| x.min(ctx) == x.max(ctx))
+            .map(|x| i64::from(x.min(ctx)))
+            .sum();
+
+        let value = i64::from(self.rhs) - sum_of_assigned;
+
+        match last {
+            Some(x) => match i32::try_from(value) {
+                Ok(value) => x.try_remove_value(value, ctx),
+                Err(_) => Some(()),
+            },
+            None => (value != 0).then_some(()),
+        }
+    }
+}
+
+impl<V: View> Prune for Linear<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        match self.relation {
+            Relation::Le => self.prune_max(ctx),
+            Relation::Ge => self.prune_min(ctx),
+            Relation::Eq => {
+                self.prune_max(ctx)?;
+                self.prune_min(ctx)
+            }
+            Relation::Ne => self.prune_not_equals(ctx),
+        }
+    }
+}
+
+impl<V: View> Propagate for Linear<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().filter_map(|x| x.get_underlying_var())
+    }
+}
@@ -0,0 +1,245 @@
+use crate::vars::{VarId, VarIdBinary};
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Negation of a binary variable: `b == !x`.
+#[derive(Clone, Debug)]
+pub struct Not {
+    x: VarIdBinary,
+    b: VarIdBinary,
+}
+
+impl Not {
+    pub const fn new(x: VarIdBinary, b: VarIdBinary) -> Self {
+        Self { x, b }
+    }
+}
+
+impl Prune for Not {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let _min = self.b.try_set_min(1 - self.x.max(ctx), ctx)?;
+        let _max = self.b.try_set_max(1 - self.x.min(ctx), ctx)?;
+        let _min = self.x.try_set_min(1 - self.b.max(ctx), ctx)?;
+        let _max = self.x.try_set_max(1 - self.b.min(ctx), ctx)?;
+
+        Some(())
+    }
+}
+
+impl Propagate for Not {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        [self.x, self.b]
+            .into_iter()
+            .filter_map(View::get_underlying_var)
+    }
+}
+
+/// Conjunction of binary variables: `b == (xs[0] && xs[1] && ... && xs[n - 1])`.
+#[derive(Clone, Debug)]
+pub struct And(Conjunction);
+
+impl And {
+    pub fn new(xs: Vec<VarIdBinary>, b: VarIdBinary) -> Self {
+        Self(Conjunction::new(xs, b, 1))
+    }
+}
+
+impl Prune for And {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        self.0.prune(ctx)
+    }
+}
+
+impl Propagate for And {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.0.list_trigger_vars()
+    }
+}
+
+/// Disjunction of binary variables: `b == (xs[0] || xs[1] || ... || xs[n - 1])`.
+#[derive(Clone, Debug)]
+pub struct Or(Conjunction);
+
+impl Or {
+    pub fn new(xs: Vec<VarIdBinary>, b: VarIdBinary) -> Self {
+        // Disjunction is unset if and only if all operands are, which is a conjunction of zeros
+        Self(Conjunction::new(xs, b, 0))
+    }
+}
+
+impl Prune for Or {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        self.0.prune(ctx)
+    }
+}
+
+impl Propagate for Or {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.0.list_trigger_vars()
+    }
+}
+
+/// Result `b` is equal to `value` if and only if all operands `xs` are.
+///
+/// Operands already assigned to `value` cannot decide the result anymore. Two watched operands
+/// that still can are tracked, and only they schedule the propagator along with the result:
+/// as long as neither of them gets assigned to `value`, there is nothing new to deduce.
+#[derive(Clone, Debug)]
+struct Conjunction {
+    xs: Vec<VarIdBinary>,
+    b: VarIdBinary,
+    value: i32,
+    watches: [usize; 2],
+}
+
+impl Conjunction {
+    fn new(xs: Vec<VarIdBinary>, b: VarIdBinary, value: i32) -> Self {
+        Self {
+            watches: [0, xs.len().saturating_sub(1)],
+            xs,
+            b,
+            value,
+        }
+    }
+
+    /// Determine if an operand is assigned to the value, and can no longer decide the result.
+    fn is_settled(&self, i: usize, ctx: &Context) -> bool {
+        !self.xs[i].contains(1 - self.value, ctx)
+    }
+
+    /// Move a watch to another operand that is not settled, if there is one.
+    fn try_move_watch(&mut self, w: usize, ctx: &mut Context) -> bool {
+        let n = self.xs.len();
+        let [current, other] = [self.watches[w], self.watches[1 - w]];
+
+        // Search resumes after the current watch, so that settled operands are not scanned again
+        let replacement = (1..n)
+            .map(|k| (current + k) % n)
+            .find(|&i| i != other && !self.is_settled(i, ctx));
+
+        if let Some(i) = replacement {
+            self.watches[w] = i;
+            ctx.move_trigger_var(self.xs[current].0, self.xs[i].0);
+        }
+
+        replacement.is_some()
+    }
+
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let value = self.value;
+
+        // Conjunction of no operands always holds
+        if self.xs.is_empty() {
+            return assign(self.b, value, ctx);
+        }
+
+        // Result set to the value requires all operands to take it as well
+        if !self.b.contains(1 - value, ctx) {
+            for &x in &self.xs {
+                assign(x, value, ctx)?;
+            }
+
+            return Some(());
+        }
+
+        for w in 0..2 {
+            if self.is_settled(self.watches[w], ctx) {
+                let _moved = self.try_move_watch(w, ctx);
+            }
+        }
+
+        let [i, j] = self.watches;
+
+        // A single operand that cannot take the value is enough to rule it out for the result
+        if !self.xs[i].contains(value, ctx) || !self.xs[j].contains(value, ctx) {
+            return assign(self.b, 1 - value, ctx);
+        }
+
+        // Watches only remain on settled operands once all operands are settled
+        match (self.is_settled(i, ctx), self.is_settled(j, ctx)) {
+            (true, true) => assign(self.b, value, ctx),
+            (true, false) if !self.b.contains(value, ctx) => assign(self.xs[j], 1 - value, ctx),
+            (false, true) if !self.b.contains(value, ctx) => assign(self.xs[i], 1 - value, ctx),
+            _ => Some(()),
+        }
+    }
+
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> + '_ {
+        // Conjunction with a single operand watches it twice, but needs to be scheduled only once
+        let n_watched = self.xs.len().min(2);
+
+        self.watches[..n_watched]
+            .iter()
+            .map(|&i| self.xs[i].0)
+            .chain([self.b.0])
+    }
+}
+
+/// Exclusive disjunction of two binary variables: `b == (x != y)`.
+///
+/// Nothing can be deduced until at most one of `x`, `y` and `b` is unassigned: two of them are
+/// watched, and only they schedule the propagator.
+#[derive(Clone, Debug)]
+pub struct Xor {
+    xs: [VarIdBinary; 3],
+    watches: [usize; 2],
+}
+
+impl Xor {
+    pub const fn new(x: VarIdBinary, y: VarIdBinary, b: VarIdBinary) -> Self {
+        Self {
+            xs: [x, y, b],
+            watches: [0, 1],
+        }
+    }
+}
+
+impl Prune for Xor {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let is_assigned = |x: VarIdBinary, ctx: &Context| x.min(ctx) == x.max(ctx);
+
+        // Assigned watch moves to the last variable, as long as it is unassigned
+        for w in 0..2 {
+            let [current, other] = [self.watches[w], self.watches[1 - w]];
+            let last = 3 - current - other;
+
+            if is_assigned(self.xs[current], ctx) && !is_assigned(self.xs[last], ctx) {
+                self.watches[w] = last;
+                ctx.move_trigger_var(self.xs[current].0, self.xs[last].0);
+            }
+        }
+
+        // Values of `x`, `y` and `b` always add up to an even number
+        let mut parity = 0;
+        let mut free = Vec::new();
+
+        for x in self.xs {
+            if is_assigned(x, ctx) {
+                parity ^= x.min(ctx);
+            } else {
+                free.push(x);
+            }
+        }
+
+        match free[..] {
+            [] if parity != 0 => None,
+            [x] => assign(x, parity, ctx),
+            _ => Some(()),
+        }
+    }
+}
+
+impl Propagate for Xor {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.watches.map(|i| self.xs[i].0).into_iter()
+    }
+}
+
+/// Assign a binary variable to the provided value, failing the space if it cannot take it.
+fn assign(x: VarIdBinary, value: i32, ctx: &mut Context) -> Option<()> {
+    let _min = x.try_set_min(value, ctx)?;
+    let _max = x.try_set_max(value, ctx)?;
+
+    Some(())
+}
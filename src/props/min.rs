@@ -0,0 +1,51 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Minimum of a list of views: `min(xs) == s`.
+#[derive(Clone, Debug)]
+pub struct Min<V, W> {
+    xs: Vec<V>,
+    s: W,
+}
+
+impl<V, W> Min<V, W> {
+    pub const fn new(xs: Vec<V>, s: W) -> Self {
+        Self { xs, s }
+    }
+}
+
+impl<V: View, W: View> Prune for Min<V, W> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Minimum of an empty list is undefined
+        let min_of_mins = self.xs.iter().map(|x| x.min(ctx)).min()?;
+        let min_of_maxs = self.xs.iter().map(|x| x.max(ctx)).min()?;
+
+        let s_min = self.s.try_set_min(min_of_mins, ctx)?;
+        let s_max = self.s.try_set_max(min_of_maxs, ctx)?;
+
+        // No term can be lower than the minimum
+        for x in &self.xs {
+            let _min = x.try_set_min(s_min, ctx)?;
+        }
+
+        // When a single term can reach the minimum, it must be the one to do so
+        let mut candidates = self.xs.iter().filter(|x| x.min(ctx) <= s_max);
+
+        if let (Some(x), None) = (candidates.next(), candidates.next()) {
+            let _max = x.try_set_max(s_max, ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<V: View, W: View> Propagate for Min<V, W> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs
+            .iter()
+            .filter_map(|x| x.get_underlying_var())
+            .chain(self.s.get_underlying_var())
+    }
+}
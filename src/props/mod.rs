@@ -1,18 +1,47 @@
+mod abs;
 mod add;
+mod alldiff;
+mod circuit;
+mod clause;
+mod cumulative;
+mod disjunctive;
+mod div;
+mod element;
 mod eq;
+mod graph;
 mod leq;
+mod linear;
+mod logic;
+mod min;
+mod modulo;
+mod mul;
+mod neq;
+mod regular;
+mod reif;
 mod sum;
+mod table;
 
 use core::ops::{Index, IndexMut};
 
 use dyn_clone::{clone_trait_object, DynClone};
 
-use crate::vars::VarId;
+use crate::vars::{VarId, VarIdBinary};
 use crate::views::{Context, View, ViewExt};
 
+pub use self::cumulative::Task;
+pub use self::linear::Relation;
+pub use self::mul::{mul_bounds, saturate};
+pub use self::regular::Dfa;
+pub use self::reif::Reification;
+
 /// Enforce a specific constraint by pruning domain of decision variables.
-pub trait Prune: core::fmt::Debug + DynClone {
+///
+/// Implementors must be `Clone`, since propagator state is copied whenever search branches,
+/// and `Send`, since spaces may be explored by another thread during parallel search.
+pub trait Prune: core::fmt::Debug + DynClone + Send {
     /// Perform pruning based on variable domains and internal state.
+    ///
+    /// The `None` case signals that the constraint cannot be satisfied in the current space.
     fn prune(&mut self, ctx: &mut Context) -> Option<()>;
 }
 
@@ -43,31 +72,174 @@ impl Propagators {
         (0..self.state.len()).map(PropId)
     }
 
+    /// Count registered propagators.
+    pub fn get_prop_count(&self) -> usize {
+        self.state.len()
+    }
+
     /// Acquire mutable reference to propagator state.
     pub fn get_state_mut(&mut self, p: PropId) -> &mut Box<dyn Prune> {
         &mut self.state[p]
     }
 
-    /// Get list of propagators that should be scheduled when a bound of variable `v` changes.
-    pub fn on_bound_change(&self, v: VarId) -> impl Iterator<Item = PropId> + '_ {
+    /// Get list of propagators that should be scheduled when the domain of variable `v` changes.
+    pub fn on_domain_change(&self, v: VarId) -> impl Iterator<Item = PropId> + '_ {
         self.dependencies[v].iter().copied()
     }
 
+    /// Schedule propagator `p` on changes to variable `to`, instead of changes to variable `from`.
+    pub fn move_trigger_var(&mut self, p: PropId, from: VarId, to: VarId) {
+        if let Some(i) = self.dependencies[from].iter().position(|&q| q == p) {
+            let _p = self.dependencies[from].swap_remove(i);
+        }
+
+        self.dependencies[to].push(p);
+    }
+
+    /// Count propagators that depend on variable `v`, among the first `n_props` registered.
+    pub fn get_degree(&self, v: VarId, n_props: usize) -> usize {
+        self.dependencies[v]
+            .iter()
+            .filter(|p| p.0 < n_props)
+            .count()
+    }
+
+    /// Sum weights of propagators that depend on variable `v`, ignoring ones without a weight.
+    pub fn get_weighted_degree(&self, v: VarId, weights: &[u32]) -> u64 {
+        self.dependencies[v]
+            .iter()
+            .filter_map(|p| weights.get(p.0))
+            .map(|&w| u64::from(w))
+            .sum()
+    }
+
+    /// Declare a user-defined propagator.
+    pub fn custom(&mut self, state: impl Propagate) -> PropId {
+        self.push_new_prop(state)
+    }
+
     /// Declare a new propagator to enforce `x + y == s`.
     pub fn add(&mut self, x: impl View, y: impl View, s: VarId) -> PropId {
         self.push_new_prop(self::add::Add::new(x, y, s))
     }
 
+    /// Declare a new propagator to enforce `x * y == s`.
+    pub fn mul(&mut self, x: impl View, y: impl View, s: VarId) -> PropId {
+        self.push_new_prop(self::mul::Mul::new(x, y, s))
+    }
+
+    /// Declare a new propagator to enforce `x / y == s`, rounding towards zero.
+    pub fn div(&mut self, x: impl View, y: impl View, s: VarId) -> PropId {
+        self.push_new_prop(self::div::Div::new(x, y, s))
+    }
+
+    /// Declare a new propagator to enforce `x % y == s`, with the sign of `x`.
+    pub fn modulo(&mut self, x: impl View, y: impl View, s: VarId) -> PropId {
+        self.push_new_prop(self::modulo::Modulo::new(x, y, s))
+    }
+
+    /// Declare a new propagator to enforce `|x| == s`.
+    pub fn abs(&mut self, x: impl View, s: VarId) -> PropId {
+        self.push_new_prop(self::abs::Abs::new(x, s))
+    }
+
     /// Declare a new propagator to enforce `sum(xs) == s`.
     pub fn sum(&mut self, xs: Vec<impl View>, s: VarId) -> PropId {
         self.push_new_prop(self::sum::Sum::new(xs, s))
     }
 
+    /// Declare a new propagator to enforce `sum(x) <op> rhs`.
+    pub fn linear(&mut self, xs: Vec<impl View>, relation: Relation, rhs: i32) -> PropId {
+        self.push_new_prop(self::linear::Linear::new(xs, relation, rhs))
+    }
+
+    /// Declare a new propagator to enforce `array[index] == s`.
+    pub fn element(&mut self, index: VarId, array: Vec<i32>, s: VarId) -> PropId {
+        self.push_new_prop(self::element::Element::new(index, array, s))
+    }
+
+    /// Declare a new propagator to enforce `array[index] == s`, for an array of views.
+    pub fn element_var(&mut self, index: VarId, array: Vec<impl View>, s: VarId) -> PropId {
+        self.push_new_prop(self::element::ElementVar::new(index, array, s))
+    }
+
+    /// Declare a new propagator to enforce `xs[i] != xs[j]` with bounds consistency.
+    pub fn all_different(&mut self, xs: Vec<impl View>) -> PropId {
+        self.push_new_prop(self::alldiff::AllDifferentBounds::new(xs))
+    }
+
+    /// Declare a new propagator to enforce `xs[i] != xs[j]` with domain consistency.
+    pub fn all_different_domain(&mut self, xs: Vec<impl View>) -> PropId {
+        self.push_new_prop(self::alldiff::AllDifferentDomain::new(xs))
+    }
+
+    /// Declare a new propagator to enforce `xs in tuples`.
+    pub fn table(&mut self, xs: Vec<VarId>, tuples: &[Vec<i32>]) -> PropId {
+        self.push_new_prop(self::table::Table::new(xs, tuples))
+    }
+
+    /// Declare a new propagator to enforce `xs not in tuples`.
+    pub fn negative_table(&mut self, xs: Vec<VarId>, tuples: &[Vec<i32>]) -> PropId {
+        self.push_new_prop(self::table::NegativeTable::new(xs, tuples))
+    }
+
+    /// Declare a new propagator to enforce that tasks never use more than `capacity` at once.
+    pub fn cumulative(&mut self, tasks: Vec<Task<impl View>>, capacity: i32) -> PropId {
+        self.push_new_prop(self::cumulative::Cumulative::new(tasks, capacity))
+    }
+
+    /// Declare a new propagator to order tasks that need more energy than a time window offers.
+    pub fn cumulative_edge_finding(
+        &mut self,
+        tasks: Vec<Task<impl View>>,
+        capacity: i32,
+    ) -> PropId {
+        self.push_new_prop(self::cumulative::CumulativeEdgeFinding::new(
+            tasks, capacity,
+        ))
+    }
+
+    /// Declare a new propagator to enforce that `i -> xs[i]` arcs form a single cycle.
+    pub fn circuit(&mut self, xs: Vec<VarId>) -> PropId {
+        self.push_new_prop(self::circuit::Circuit::new(xs))
+    }
+
+    /// Declare a new propagator to enforce that `i -> xs[i]` arcs form a cycle and self-loops.
+    pub fn subcircuit(&mut self, xs: Vec<VarId>) -> PropId {
+        self.push_new_prop(self::circuit::Subcircuit::new(xs))
+    }
+
+    /// Declare a new propagator to enforce that tasks never overlap.
+    pub fn disjunctive(&mut self, starts: Vec<impl View>, durations: Vec<i32>) -> PropId {
+        self.push_new_prop(self::disjunctive::Disjunctive::new(starts, durations))
+    }
+
+    /// Declare a new propagator to enforce `xs in L(dfa)`.
+    pub fn regular(&mut self, xs: Vec<VarId>, dfa: Dfa) -> PropId {
+        self.push_new_prop(self::regular::Regular::new(xs, dfa))
+    }
+
+    /// Declare a new propagator to enforce `min(xs) == s`.
+    pub fn min(&mut self, xs: Vec<impl View>, s: impl View) -> PropId {
+        self.push_new_prop(self::min::Min::new(xs, s))
+    }
+
+    /// Declare a new propagator to enforce `max(xs) == s`.
+    pub fn max(&mut self, xs: Vec<impl View>, s: impl View) -> PropId {
+        let xs = xs.into_iter().map(ViewExt::opposite).collect();
+        self.min(xs, s.opposite())
+    }
+
     /// Declare a new propagator to enforce `x == y`.
     pub fn equals(&mut self, x: impl View, y: impl View) -> PropId {
         self.push_new_prop(self::eq::Equals::new(x, y))
     }
 
+    /// Declare a new propagator to enforce `x != y`.
+    pub fn not_equals(&mut self, x: impl View, y: impl View) -> PropId {
+        self.push_new_prop(self::neq::NotEquals::new(x, y))
+    }
+
     /// Declare a new propagator to enforce `x <= y`.
     pub fn less_than_or_equals(&mut self, x: impl View, y: impl View) -> PropId {
         self.push_new_prop(self::leq::LessThanOrEquals::new(x, y))
@@ -88,6 +260,99 @@ impl Propagators {
         self.greater_than_or_equals(x, y.plus(1))
     }
 
+    /// Declare a new propagator to enforce that at least one literal holds.
+    ///
+    /// Literals are binary variables with a polarity: `true` if they should be set, `false` if not.
+    pub fn clause(&mut self, literals: Vec<(VarIdBinary, bool)>) -> PropId {
+        self.push_new_prop(self::clause::Clause::new(literals))
+    }
+
+    /// Declare a new propagator to enforce `b == !x`.
+    pub fn not(&mut self, x: VarIdBinary, b: VarIdBinary) -> PropId {
+        self.push_new_prop(self::logic::Not::new(x, b))
+    }
+
+    /// Declare a new propagator to enforce `b == (xs[0] && xs[1] && ... && xs[n - 1])`.
+    pub fn and(&mut self, xs: Vec<VarIdBinary>, b: VarIdBinary) -> PropId {
+        self.push_new_prop(self::logic::And::new(xs, b))
+    }
+
+    /// Declare a new propagator to enforce `b == (xs[0] || xs[1] || ... || xs[n - 1])`.
+    pub fn or(&mut self, xs: Vec<VarIdBinary>, b: VarIdBinary) -> PropId {
+        self.push_new_prop(self::logic::Or::new(xs, b))
+    }
+
+    /// Declare a new propagator to enforce `b == (x != y)`.
+    pub fn xor(&mut self, x: VarIdBinary, y: VarIdBinary, b: VarIdBinary) -> PropId {
+        self.push_new_prop(self::logic::Xor::new(x, y, b))
+    }
+
+    /// Declare a new propagator to link binary variable `b` to constraint `x == y`.
+    pub fn reify_equals(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+        r: Reification,
+    ) -> PropId {
+        self.push_new_prop(self::reif::Reified::equals(b, x, y, r))
+    }
+
+    /// Declare a new propagator to link binary variable `b` to constraint `x != y`.
+    pub fn reify_not_equals(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+        r: Reification,
+    ) -> PropId {
+        self.push_new_prop(self::reif::Reified::not_equals(b, x, y, r))
+    }
+
+    /// Declare a new propagator to link binary variable `b` to constraint `x <= y`.
+    pub fn reify_less_than_or_equals(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+        r: Reification,
+    ) -> PropId {
+        self.push_new_prop(self::reif::Reified::less_than_or_equals(b, x, y, r))
+    }
+
+    /// Declare a new propagator to link binary variable `b` to constraint `x < y`.
+    pub fn reify_less_than(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+        r: Reification,
+    ) -> PropId {
+        self.reify_less_than_or_equals(b, x.plus(1), y, r)
+    }
+
+    /// Declare a new propagator to link binary variable `b` to constraint `x >= y`.
+    pub fn reify_greater_than_or_equals(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+        r: Reification,
+    ) -> PropId {
+        self.reify_less_than_or_equals(b, y, x, r)
+    }
+
+    /// Declare a new propagator to link binary variable `b` to constraint `x > y`.
+    pub fn reify_greater_than(
+        &mut self,
+        b: VarIdBinary,
+        x: impl View,
+        y: impl View,
+        r: Reification,
+    ) -> PropId {
+        self.reify_greater_than_or_equals(b, x, y.plus(1), r)
+    }
+
     /// Register propagator dependencies and store its state as a trait object.
     fn push_new_prop(&mut self, state: impl Propagate) -> PropId {
         // Create new handle to refer to propagator state and dependencies
@@ -107,7 +372,7 @@ impl Propagators {
 
 /// Propagator handle that is not bound to a specific memory location.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub struct PropId(usize);
+pub struct PropId(pub(crate) usize);
 
 impl Index<PropId> for Vec<Box<dyn Prune>> {
     type Output = Box<dyn Prune>;
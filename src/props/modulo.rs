@@ -0,0 +1,85 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{saturate, Propagate, Prune};
+
+/// Remainder of a division that rounds towards zero, like Rust's `%` operator: `x % y == s`.
+#[derive(Clone, Copy, Debug)]
+pub struct Modulo<U, V> {
+    x: U,
+    y: V,
+    s: VarId,
+}
+
+impl<U, V> Modulo<U, V> {
+    pub const fn new(x: U, y: V, s: VarId) -> Self {
+        Self { x, y, s }
+    }
+}
+
+impl<U: View, V: View> Prune for Modulo<U, V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Division by zero is undefined
+        self.y.try_remove_value(0, ctx)?;
+
+        let (x_min, x_max) = (i64::from(self.x.min(ctx)), i64::from(self.x.max(ctx)));
+        let (y_min, y_max) = (i64::from(self.y.min(ctx)), i64::from(self.y.max(ctx)));
+
+        // Remainder is computed exactly once both operands are assigned
+        if x_min == x_max && y_min == y_max {
+            let r = saturate(x_min % y_min);
+
+            let _min = self.s.try_set_min(r, ctx)?;
+            let _max = self.s.try_set_max(r, ctx)?;
+
+            return Some(());
+        }
+
+        // Remainder has the sign of the dividend, and is smaller than the divisor in magnitude
+        let y_abs_max = y_min.abs().max(y_max.abs());
+
+        let min = x_min.min(0).max(1 - y_abs_max);
+        let max = x_max.max(0).min(y_abs_max - 1);
+
+        let s_min = self.s.try_set_min(saturate(min), ctx)?;
+        let s_max = self.s.try_set_max(saturate(max), ctx)?;
+
+        // A non-zero remainder constrains the sign of the dividend, and bounds its magnitude
+        if s_min > 0 {
+            let _min = self.x.try_set_min(s_min, ctx)?;
+        }
+
+        if s_max < 0 {
+            let _max = self.x.try_set_max(s_max, ctx)?;
+        }
+
+        // Divisor must be larger than the remainder in magnitude, which may not fit in an `i32`
+        let s_abs_min = if s_min > 0 {
+            i64::from(s_min)
+        } else if s_max < 0 {
+            -i64::from(s_max)
+        } else {
+            0
+        };
+
+        if i64::from(self.y.min(ctx)) > -s_abs_min - 1 {
+            let min = i32::try_from(s_abs_min + 1).ok()?;
+            let _min = self.y.try_set_min(min, ctx)?;
+        }
+
+        if i64::from(self.y.max(ctx)) < s_abs_min + 1 {
+            let max = i32::try_from(-s_abs_min - 1).ok()?;
+            let _max = self.y.try_set_max(max, ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<U: View, V: View> Propagate for Modulo<U, V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        core::iter::once(self.s)
+            .chain(self.x.get_underlying_var())
+            .chain(self.y.get_underlying_var())
+    }
+}
@@ -0,0 +1,111 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Multiply two views together: `x * y == s`.
+#[derive(Clone, Copy, Debug)]
+pub struct Mul<U, V> {
+    x: U,
+    y: V,
+    s: VarId,
+}
+
+impl<U, V> Mul<U, V> {
+    pub const fn new(x: U, y: V, s: VarId) -> Self {
+        Self { x, y, s }
+    }
+}
+
+impl<U: View, V: View> Prune for Mul<U, V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Product bounds are reached on corners of the domains of both factors
+        let (min, max) = mul_bounds(bounds(self.x, ctx), bounds(self.y, ctx));
+
+        let _min = self.s.try_set_min(saturate(min), ctx)?;
+        let _max = self.s.try_set_max(saturate(max), ctx)?;
+
+        // A non-zero product rules out zero for both factors
+        if !self.s.contains(0, ctx) {
+            self.x.try_remove_value(0, ctx)?;
+            self.y.try_remove_value(0, ctx)?;
+        }
+
+        self.prune_factor(self.x, self.y, ctx)?;
+        self.prune_factor(self.y, self.x, ctx)?;
+
+        Some(())
+    }
+}
+
+impl<U: View, V: View> Mul<U, V> {
+    /// Derive bounds of `factor` from `s / other`.
+    fn prune_factor(&self, factor: impl View, other: impl View, ctx: &mut Context) -> Option<()> {
+        // Nothing can be deduced if both the product and the other factor can be zero
+        if self.s.contains(0, ctx) && other.contains(0, ctx) {
+            return Some(());
+        }
+
+        let s = bounds(self.s, ctx);
+        let (other_min, other_max) = bounds(other, ctx);
+
+        // Split other factor on both sides of zero, to divide by intervals of constant sign
+        let neg = (other_min, other_max.min(-1));
+        let pos = (other_min.max(1), other_max);
+
+        let (min, max) = [neg, pos]
+            .into_iter()
+            .filter(|&(min, max)| min <= max)
+            .map(|other| div_bounds(s, other))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))?;
+
+        let _min = factor.try_set_min(saturate(min), ctx)?;
+        let _max = factor.try_set_max(saturate(max), ctx)?;
+
+        Some(())
+    }
+}
+
+impl<U: View, V: View> Propagate for Mul<U, V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        core::iter::once(self.s)
+            .chain(self.x.get_underlying_var())
+            .chain(self.y.get_underlying_var())
+    }
+}
+
+/// Domain bounds of a view, widened to avoid overflows in intermediate computations.
+fn bounds(x: impl View, ctx: &Context) -> (i64, i64) {
+    (i64::from(x.min(ctx)), i64::from(x.max(ctx)))
+}
+
+/// Bounds of the product of two intervals.
+pub fn mul_bounds((x_min, x_max): (i64, i64), (y_min, y_max): (i64, i64)) -> (i64, i64) {
+    let corners = [x_min * y_min, x_min * y_max, x_max * y_min, x_max * y_max];
+
+    let min = corners.into_iter().min().unwrap_or_default();
+    let max = corners.into_iter().max().unwrap_or_default();
+
+    (min, max)
+}
+
+/// Bounds of integers `q` such that `q * y == s`, for an interval `y` that excludes zero.
+fn div_bounds((s_min, s_max): (i64, i64), (y_min, y_max): (i64, i64)) -> (i64, i64) {
+    let corners = [
+        (s_min, y_min),
+        (s_min, y_max),
+        (s_max, y_min),
+        (s_max, y_max),
+    ];
+
+    // Rounding each corner inwards preserves the extremes of the real-valued quotient
+    let min = corners.into_iter().map(|(s, y)| s.div_ceil(y)).min();
+    let max = corners.into_iter().map(|(s, y)| s.div_floor(y)).max();
+
+    (min.unwrap_or_default(), max.unwrap_or_default())
+}
+
+/// Narrow bound to fit in the domain of decision variables, saturating on overflow.
+pub fn saturate(bound: i64) -> i32 {
+    i32::try_from(bound.clamp(i32::MIN.into(), i32::MAX.into())).unwrap_or_default()
+}
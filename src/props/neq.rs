@@ -0,0 +1,54 @@
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::reif::Entail;
+use super::{Propagate, Prune};
+
+/// Enforce disequality between two views: `x != y`.
+#[derive(Clone, Copy, Debug)]
+pub struct NotEquals<U, V> {
+    x: U,
+    y: V,
+}
+
+impl<U, V> NotEquals<U, V> {
+    pub const fn new(x: U, y: V) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<U: View, V: View> Prune for NotEquals<U, V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Values can only be ruled out once the other side is assigned
+        if self.x.min(ctx) == self.x.max(ctx) {
+            self.y.try_remove_value(self.x.min(ctx), ctx)?;
+        }
+
+        if self.y.min(ctx) == self.y.max(ctx) {
+            self.x.try_remove_value(self.y.min(ctx), ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<U: View, V: View> Propagate for NotEquals<U, V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.x
+            .get_underlying_var()
+            .into_iter()
+            .chain(self.y.get_underlying_var())
+    }
+}
+
+impl<U: View, V: View> Entail for NotEquals<U, V> {
+    fn is_entailed(&self, ctx: &Context) -> bool {
+        let (x, y) = (self.x, self.y);
+
+        // Bounds do not overlap, or one side is assigned to a value the other cannot take
+        x.max(ctx) < y.min(ctx)
+            || y.max(ctx) < x.min(ctx)
+            || (x.min(ctx) == x.max(ctx) && !y.contains(x.min(ctx), ctx))
+            || (y.min(ctx) == y.max(ctx) && !x.contains(y.min(ctx), ctx))
+    }
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use crate::vars::VarId;
+use crate::views::{Context, View};
+
+use super::{Propagate, Prune};
+
+/// Deterministic finite automaton, which accepts or rejects sequences of values.
+///
+/// States are numbered from zero. Each transition reads a value and moves from one state to
+/// another, and a sequence is accepted if reading it from the initial state ends in an
+/// accepting state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dfa {
+    initial: usize,
+    accepting: Vec<bool>,
+
+    /// Outgoing transitions of each state, sorted by value.
+    transitions: Vec<Vec<(i32, usize)>>,
+}
+
+impl Dfa {
+    /// Create an automaton from its list of transitions, as `(from, value, to)` triples.
+    ///
+    /// This function will only create an automaton if all states are below `n_states`,
+    /// and if no state has two transitions for the same value to different states.
+    #[must_use]
+    pub fn new(
+        n_states: usize,
+        initial: usize,
+        accepting: &[usize],
+        transitions: &[(usize, i32, usize)],
+    ) -> Option<Self> {
+        if initial >= n_states {
+            return None;
+        }
+
+        let mut is_accepting = vec![false; n_states];
+        for &state in accepting {
+            *is_accepting.get_mut(state)? = true;
+        }
+
+        let mut outgoing = vec![Vec::new(); n_states];
+        for &(from, value, to) in transitions {
+            if to >= n_states {
+                return None;
+            }
+
+            outgoing.get_mut(from)?.push((value, to));
+        }
+
+        // Duplicate transitions are harmless, conflicting ones make the automaton nondeterministic
+        for ts in &mut outgoing {
+            ts.sort_unstable();
+            ts.dedup();
+
+            if ts.windows(2).any(|w| w[0].0 == w[1].0) {
+                return None;
+            }
+        }
+
+        Some(Self {
+            initial,
+            accepting: is_accepting,
+            transitions: outgoing,
+        })
+    }
+
+    /// Number of states of the automaton.
+    #[must_use]
+    pub fn get_state_count(&self) -> usize {
+        self.accepting.len()
+    }
+
+    /// Determine if the automaton accepts the provided sequence of values.
+    #[must_use]
+    pub fn accepts(&self, values: &[i32]) -> bool {
+        let mut state = self.initial;
+
+        for value in values {
+            let ts = &self.transitions[state];
+
+            match ts.binary_search_by_key(value, |&(v, _)| v) {
+                Ok(i) => state = ts[i].1,
+                Err(_) => return false,
+            }
+        }
+
+        self.accepting[state]
+    }
+}
+
+/// Restrict a sequence of variables to words accepted by an automaton: `xs in L(dfa)`.
+///
+/// Generalized arc consistency follows Pesant, "A Regular Language Membership Constraint for
+/// Finite Sequences of Variables" (CP 2004). The layered graph is rebuilt on each run,
+/// since propagator state is copied on branch.
+#[derive(Clone, Debug)]
+pub struct Regular {
+    xs: Vec<VarId>,
+    dfa: Arc<Dfa>,
+}
+
+impl Regular {
+    pub fn new(xs: Vec<VarId>, dfa: Dfa) -> Self {
+        Self {
+            xs,
+            dfa: Arc::new(dfa),
+        }
+    }
+}
+
+impl Prune for Regular {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        let n = self.xs.len();
+        let n_states = self.dfa.get_state_count();
+
+        // Forward pass: states reachable from the initial one after each prefix
+        let mut forward = vec![vec![false; n_states]; n + 1];
+        forward[0][self.dfa.initial] = true;
+
+        for (layer, &x) in self.xs.iter().enumerate() {
+            for state in 0..n_states {
+                if !forward[layer][state] {
+                    continue;
+                }
+
+                for &(value, to) in &self.dfa.transitions[state] {
+                    if x.contains(value, ctx) {
+                        forward[layer + 1][to] = true;
+                    }
+                }
+            }
+        }
+
+        // Backward pass: reachable states that lead to an accepting one after each suffix
+        let mut backward = vec![vec![false; n_states]; n + 1];
+        for state in 0..n_states {
+            backward[n][state] = forward[n][state] && self.dfa.accepting[state];
+        }
+
+        // Values supported by an edge of the layered graph on a path from source to sink
+        let mut supported = vec![Vec::new(); n];
+
+        for (layer, &x) in self.xs.iter().enumerate().rev() {
+            for state in 0..n_states {
+                if !forward[layer][state] {
+                    continue;
+                }
+
+                for &(value, to) in &self.dfa.transitions[state] {
+                    if backward[layer + 1][to] && x.contains(value, ctx) {
+                        backward[layer][state] = true;
+                        supported[layer].push(value);
+                    }
+                }
+            }
+        }
+
+        // Sequence cannot reach any accepting state
+        if !backward[0][self.dfa.initial] {
+            return None;
+        }
+
+        for (&x, values) in self.xs.iter().zip(&mut supported) {
+            values.sort_unstable();
+
+            let domain: Vec<_> = ctx.iter_values(x).collect();
+
+            for value in domain {
+                if values.binary_search(&value).is_err() {
+                    ctx.try_remove_value(x, value)?;
+                }
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl Propagate for Regular {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().copied()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::vars::{VarId, VarIdBinary};
+use crate::views::{Context, Plus, View, ViewExt};
+
+use super::eq::Equals;
+use super::leq::LessThanOrEquals;
+use super::neq::NotEquals;
+use super::{Propagate, Prune};
+
+/// Strength of the link between a binary variable and the truth value of a constraint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reification {
+    /// Binary variable is set if and only if the constraint holds: `b <-> c`.
+    Full,
+
+    /// Binary variable being set forces the constraint to hold: `b -> c`.
+    Half,
+}
+
+/// Constraint that can detect when it holds for all remaining assignments.
+pub trait Entail: Propagate + Clone {
+    /// Determine if all assignments left in the domains of involved variables satisfy the constraint.
+    fn is_entailed(&self, ctx: &Context) -> bool;
+}
+
+/// Link a binary variable to the truth value of a constraint, through the constraint and its negation.
+#[derive(Clone, Debug)]
+pub struct Reified<C, N> {
+    b: VarIdBinary,
+    c: C,
+    not_c: N,
+    reification: Reification,
+}
+
+impl<C, N> Reified<C, N> {
+    pub const fn new(b: VarIdBinary, c: C, not_c: N, reification: Reification) -> Self {
+        Self {
+            b,
+            c,
+            not_c,
+            reification,
+        }
+    }
+}
+
+impl<U: View, V: View> Reified<Equals<U, V>, NotEquals<U, V>> {
+    /// Link binary variable to constraint `x == y`.
+    pub const fn equals(b: VarIdBinary, x: U, y: V, reification: Reification) -> Self {
+        Self::new(b, Equals::new(x, y), NotEquals::new(x, y), reification)
+    }
+}
+
+impl<U: View, V: View> Reified<NotEquals<U, V>, Equals<U, V>> {
+    /// Link binary variable to constraint `x != y`.
+    pub const fn not_equals(b: VarIdBinary, x: U, y: V, reification: Reification) -> Self {
+        Self::new(b, NotEquals::new(x, y), Equals::new(x, y), reification)
+    }
+}
+
+impl<U: View, V: View> Reified<LessThanOrEquals<U, V>, LessThanOrEquals<Plus<V>, U>> {
+    /// Link binary variable to constraint `x <= y`, whose negation is `y + 1 <= x`.
+    pub fn less_than_or_equals(b: VarIdBinary, x: U, y: V, reification: Reification) -> Self {
+        let not_c = LessThanOrEquals::new(y.plus(1), x);
+        Self::new(b, LessThanOrEquals::new(x, y), not_c, reification)
+    }
+}
+
+impl<C: Entail, N: Entail> Prune for Reified<C, N> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Constraint never holds: binary variable cannot be set
+        if self.not_c.is_entailed(ctx) {
+            let _max = self.b.try_set_max(0, ctx)?;
+        }
+
+        // Constraint always holds: only a full reification sets the binary variable
+        if self.reification == Reification::Full && self.c.is_entailed(ctx) {
+            let _min = self.b.try_set_min(1, ctx)?;
+        }
+
+        // Once the binary variable is assigned, enforce the constraint or its negation
+        if self.b.min(ctx) == 1 {
+            self.c.prune(ctx)?;
+        } else if self.b.max(ctx) == 0 && self.reification == Reification::Full {
+            self.not_c.prune(ctx)?;
+        }
+
+        Some(())
+    }
+}
+
+impl<C: Entail, N: Entail> Propagate for Reified<C, N> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.b
+            .get_underlying_var()
+            .into_iter()
+            .chain(self.c.list_trigger_vars())
+    }
+}
@@ -0,0 +1,346 @@
+use std::sync::Arc;
+
+use crate::vars::VarId;
+use crate::views::Context;
+
+use super::{Propagate, Prune};
+
+/// Restrict a list of variables to one of the allowed tuples: `xs in tuples`.
+///
+/// Generalized arc consistency follows Demeulenaere et al., "Compact-Table: Efficiently
+/// Filtering Table Constraints with Reversible Sparse Bit-Sets" (CP 2016).
+/// Domain updates are processed by resetting masks, since propagator state is copied on branch.
+#[derive(Clone, Debug)]
+pub struct Table {
+    xs: Vec<VarId>,
+    columns: Arc<[Column]>,
+    valid: SparseBitSet,
+
+    /// Domain size of each variable during the previous run, to skip unchanged ones.
+    sizes: Vec<usize>,
+
+    /// Index of the last word found to support each value of each column.
+    residues: Vec<Vec<usize>>,
+}
+
+impl Table {
+    pub fn new(xs: Vec<VarId>, tuples: &[Vec<i32>]) -> Self {
+        let (columns, n_tuples) = Column::build(xs.len(), tuples);
+
+        let residues = columns.iter().map(|c| vec![0; c.values.len()]).collect();
+
+        Self {
+            sizes: vec![0; xs.len()],
+            xs,
+            columns,
+            valid: SparseBitSet::full(n_tuples),
+            residues,
+        }
+    }
+}
+
+impl Prune for Table {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        update_valid(
+            &self.xs,
+            &self.columns,
+            &mut self.valid,
+            &mut self.sizes,
+            ctx,
+        );
+
+        // No tuple is left to satisfy the constraint
+        if self.valid.is_empty() {
+            return None;
+        }
+
+        // Remove values that no valid tuple supports
+        for (i, &x) in self.xs.iter().enumerate() {
+            let column = &self.columns[i];
+            let residues = &mut self.residues[i];
+
+            let values: Vec<_> = ctx.iter_values(x).collect();
+            let mut size = values.len();
+
+            for value in values {
+                let is_supported = column.values.binary_search(&value).is_ok_and(|j| {
+                    let supports = &column.supports[j];
+
+                    // Word that supported the value last time is likely to still support it
+                    if supports[residues[j]] & self.valid.words[residues[j]] != 0 {
+                        return true;
+                    }
+
+                    self.valid
+                        .find_intersection(supports)
+                        .map(|k| residues[j] = k)
+                        .is_some()
+                });
+
+                if !is_supported {
+                    ctx.try_remove_value(x, value)?;
+                    size -= 1;
+                }
+            }
+
+            // Filtered values had no valid tuple, they do not need to be processed again
+            self.sizes[i] = size;
+        }
+
+        Some(())
+    }
+}
+
+impl Propagate for Table {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().copied()
+    }
+}
+
+/// Forbid a list of variables from taking any of the provided tuples: `xs not in tuples`.
+///
+/// Generalized arc consistency follows Verhaeghe et al., "Extending Compact-Table to Negative
+/// and Short Tables" (AAAI 2017): a value is removed once forbidden tuples cover all
+/// assignments of the other variables.
+#[derive(Clone, Debug)]
+pub struct NegativeTable {
+    xs: Vec<VarId>,
+    columns: Arc<[Column]>,
+
+    /// Forbidden tuples that are still within the domains of all variables.
+    valid: SparseBitSet,
+
+    /// Domain size of each variable during the previous run, to skip unchanged ones.
+    sizes: Vec<usize>,
+}
+
+impl NegativeTable {
+    pub fn new(xs: Vec<VarId>, tuples: &[Vec<i32>]) -> Self {
+        let (columns, n_tuples) = Column::build(xs.len(), tuples);
+
+        Self {
+            sizes: vec![0; xs.len()],
+            xs,
+            columns,
+            valid: SparseBitSet::full(n_tuples),
+        }
+    }
+}
+
+impl Prune for NegativeTable {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        update_valid(
+            &self.xs,
+            &self.columns,
+            &mut self.valid,
+            &mut self.sizes,
+            ctx,
+        );
+
+        // Constraint is entailed once no forbidden tuple is left within domains
+        if self.valid.is_empty() {
+            return Some(());
+        }
+
+        let n_valid = self.valid.count();
+
+        for (i, &x) in self.xs.iter().enumerate() {
+            // Number of assignments of other variables, capped since it only matters when small
+            let n_assignments = self
+                .sizes
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .try_fold(1_usize, |n, (_, &size)| n.checked_mul(size))
+                .filter(|&n| n <= n_valid);
+
+            let Some(n_assignments) = n_assignments else {
+                continue;
+            };
+
+            let column = &self.columns[i];
+            let values: Vec<_> = ctx.iter_values(x).collect();
+
+            // Values are removed when every assignment of the other variables is forbidden
+            for value in values {
+                if let Ok(j) = column.values.binary_search(&value) {
+                    if self.valid.count_intersection(&column.supports[j]) == n_assignments {
+                        ctx.try_remove_value(x, value)?;
+                    }
+                }
+            }
+        }
+
+        Some(())
+    }
+}
+
+impl Propagate for NegativeTable {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.xs.iter().copied()
+    }
+}
+
+/// Remove tuples that are no longer within domains, for variables whose domain changed.
+///
+/// Domain sizes get refreshed, since they shrink monotonically along a branch.
+fn update_valid(
+    xs: &[VarId],
+    columns: &[Column],
+    valid: &mut SparseBitSet,
+    sizes: &mut [usize],
+    ctx: &Context,
+) {
+    for (i, &x) in xs.iter().enumerate() {
+        let size = ctx.iter_values(x).count();
+
+        if size == sizes[i] {
+            continue;
+        }
+
+        sizes[i] = size;
+
+        // Collect tuples supported by any of the remaining values
+        let column = &columns[i];
+        valid.clear_mask();
+
+        for value in ctx.iter_values(x) {
+            if let Ok(j) = column.values.binary_search(&value) {
+                valid.add_to_mask(&column.supports[j]);
+            }
+        }
+
+        valid.intersect_with_mask();
+    }
+}
+
+/// Tuples that hold each value at a given position, as bitsets over the list of tuples.
+#[derive(Debug)]
+struct Column {
+    values: Vec<i32>,
+    supports: Vec<Vec<u64>>,
+}
+
+impl Column {
+    /// Index distinct tuples by value for each position, ignoring tuples of the wrong length.
+    fn build(n: usize, tuples: &[Vec<i32>]) -> (Arc<[Self]>, usize) {
+        let mut tuples: Vec<_> = tuples.iter().filter(|t| t.len() == n).collect();
+        tuples.sort_unstable();
+        tuples.dedup();
+
+        let n_words = tuples.len().div_ceil(64);
+
+        let columns = (0..n)
+            .map(|i| {
+                let mut values: Vec<_> = tuples.iter().map(|t| t[i]).collect();
+                values.sort_unstable();
+                values.dedup();
+
+                let mut supports = vec![vec![0; n_words]; values.len()];
+
+                for (k, t) in tuples.iter().enumerate() {
+                    if let Ok(j) = values.binary_search(&t[i]) {
+                        supports[j][k / 64] |= 1 << (k % 64);
+                    }
+                }
+
+                Self { values, supports }
+            })
+            .collect();
+
+        (columns, tuples.len())
+    }
+}
+
+/// Bitset that keeps track of its non-zero words, to skip empty ones in all operations.
+#[derive(Clone, Debug)]
+struct SparseBitSet {
+    words: Vec<u64>,
+
+    /// Indices of words, where the first `limit` ones point to non-zero words.
+    index: Vec<usize>,
+    limit: usize,
+
+    /// Scratch bitset, used to intersect the set with a union of other bitsets.
+    mask: Vec<u64>,
+}
+
+impl SparseBitSet {
+    /// Create a set that contains all elements in `0..n`.
+    fn full(n: usize) -> Self {
+        let n_words = n.div_ceil(64);
+
+        let mut words = vec![u64::MAX; n_words];
+
+        // Bits past the last element are left unset
+        if let Some(last) = words.last_mut() {
+            if n % 64 != 0 {
+                *last = (1 << (n % 64)) - 1;
+            }
+        }
+
+        Self {
+            words,
+            index: (0..n_words).collect(),
+            limit: n_words,
+            mask: vec![0; n_words],
+        }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.limit == 0
+    }
+
+    /// Number of elements in the set.
+    fn count(&self) -> usize {
+        self.index[..self.limit]
+            .iter()
+            .map(|&w| self.words[w].count_ones() as usize)
+            .sum()
+    }
+
+    fn clear_mask(&mut self) {
+        for &w in &self.index[..self.limit] {
+            self.mask[w] = 0;
+        }
+    }
+
+    fn add_to_mask(&mut self, other: &[u64]) {
+        for &w in &self.index[..self.limit] {
+            self.mask[w] |= other[w];
+        }
+    }
+
+    /// Keep elements that are also in the mask, moving words that become empty past the limit.
+    fn intersect_with_mask(&mut self) {
+        for i in (0..self.limit).rev() {
+            let w = self.index[i];
+            let word = self.words[w] & self.mask[w];
+
+            if word != self.words[w] {
+                self.words[w] = word;
+
+                if word == 0 {
+                    self.limit -= 1;
+                    self.index.swap(i, self.limit);
+                }
+            }
+        }
+    }
+
+    /// Find a word where both sets share elements.
+    fn find_intersection(&self, other: &[u64]) -> Option<usize> {
+        self.index[..self.limit]
+            .iter()
+            .copied()
+            .find(|&w| self.words[w] & other[w] != 0)
+    }
+
+    /// Number of elements shared by both sets.
+    fn count_intersection(&self, other: &[u64]) -> usize {
+        self.index[..self.limit]
+            .iter()
+            .map(|&w| (self.words[w] & other[w]).count_ones() as usize)
+            .sum()
+    }
+}
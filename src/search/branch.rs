@@ -1,44 +1,399 @@
-use crate::props::PropId;
+use core::cmp::Reverse;
+use std::sync::Arc;
+
+use crate::props::{PropId, Propagators};
 use crate::search::Space;
-use crate::vars::VarId;
+use crate::vars::{Domain, VarId, Vars};
+use crate::views::View;
+
+/// Strategy to pick which unassigned decision variable to branch on next.
+///
+/// Ties are broken in favor of the variable created first.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum VariableSelection {
+    /// First unassigned variable, in creation order.
+    #[default]
+    InputOrder,
+
+    /// Variable with the fewest values left in its domain, also known as first-fail.
+    SmallestDomain,
+
+    /// Variable with the most values left in its domain.
+    LargestDomain,
+
+    /// Variable with the lowest domain minimum.
+    SmallestMin,
+
+    /// Variable with the lowest ratio of domain size over number of constraints it appears in.
+    DomOverDeg,
+
+    /// Like [`Self::DomOverDeg`], with constraints weighted by how often they failed during search.
+    DomOverWdeg,
+}
+
+impl VariableSelection {
+    /// Pick one of the provided unassigned decision variables, given failure weights of propagators.
+    pub(crate) fn select(
+        self,
+        mut vs: impl Iterator<Item = VarId>,
+        vars: &Vars,
+        props: &Propagators,
+        weights: &[u32],
+    ) -> Option<VarId> {
+        match self {
+            Self::InputOrder => vs.next(),
+            Self::SmallestDomain => vs.min_by_key(|&v| vars[v].size()),
+            Self::LargestDomain => vs.min_by_key(|&v| Reverse(vars[v].size())),
+            Self::SmallestMin => vs.min_by_key(|&v| vars[v].min),
+            Self::DomOverDeg => {
+                min_by_ratio(vs, vars, |v| props.get_degree(v, weights.len()) as u64)
+            }
+            Self::DomOverWdeg => min_by_ratio(vs, vars, |v| props.get_weighted_degree(v, weights)),
+        }
+    }
+}
+
+/// Find variable with the lowest ratio of domain size over the provided degree.
+///
+/// Ratios are compared by cross-multiplication, variables with a zero degree come last.
+fn min_by_ratio(
+    vs: impl Iterator<Item = VarId>,
+    vars: &Vars,
+    degree: impl Fn(VarId) -> u64,
+) -> Option<VarId> {
+    vs.map(|v| (v, u128::from(vars[v].size()), u128::from(degree(v))))
+        .min_by(|(_, size_a, deg_a), (_, size_b, deg_b)| (size_a * deg_b).cmp(&(size_b * deg_a)))
+        .map(|(v, _, _)| v)
+}
+
+/// Strategy to pick which value of the selected decision variable to explore first.
+#[derive(Clone, Default)]
+pub enum ValueSelection {
+    /// Assign the domain minimum, then exclude it on backtrack.
+    AssignMin,
+
+    /// Assign the domain maximum, then exclude it on backtrack.
+    AssignMax,
+
+    /// Restrict the domain to its lower half, then to its upper half on backtrack.
+    #[default]
+    SplitLower,
+
+    /// Restrict the domain to its upper half, then to its lower half on backtrack.
+    SplitUpper,
+
+    /// Assign the median value of the domain, then exclude it on backtrack.
+    AssignMedian,
+
+    /// Assign a value picked at random from the domain, then exclude it on backtrack.
+    ///
+    /// Search is deterministic for a given seed.
+    AssignRandom {
+        /// Initial state of the pseudo-random number generator.
+        seed: u64,
+    },
+
+    /// Assign the value returned by a user closure, then exclude it on backtrack.
+    ///
+    /// Values outside of the domain are replaced by the domain minimum.
+    Custom(Arc<ValueSelector>),
+}
+
+/// Closure to pick a value from the domain of a decision variable.
+pub type ValueSelector = dyn Fn(VarId, Domain<'_>) -> i32 + Send + Sync;
+
+impl ValueSelection {
+    /// Wrap a closure to pick values from the domain of decision variables.
+    pub fn custom(f: impl Fn(VarId, Domain<'_>) -> i32 + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Derive both alternatives to explore, in order, for the provided decision variable.
+    fn decide(&self, pivot: VarId, domain: Domain<'_>, n_branches: u64) -> [Decision; 2] {
+        let assign = |value| {
+            [
+                Decision::equals(pivot, value),
+                Decision::not_equals(pivot, value),
+            ]
+        };
+
+        let at_position = |i: u64| {
+            let i = usize::try_from(i).unwrap_or(usize::MAX);
+            domain.iter().nth(i).unwrap_or_else(|| domain.min())
+        };
 
-/// Perform a binary split on the first unassigned decision variable.
-pub fn split_on_unassigned(space: Space) -> SplitOnUnassigned {
-    if let Some(pivot) = space.vars.get_unassigned_var() {
-        // Split domain at mid-point of domain
-        let mid = space.vars[pivot].mid();
+        match self {
+            Self::AssignMin => assign(domain.min()),
+            Self::AssignMax => assign(domain.max()),
+            Self::SplitLower => {
+                let mid = domain.mid();
+                [
+                    Decision::less_than_or_equals(pivot, mid),
+                    Decision::greater_than(pivot, mid),
+                ]
+            }
+            Self::SplitUpper => {
+                let mid = domain.mid();
+                [
+                    Decision::greater_than(pivot, mid),
+                    Decision::less_than_or_equals(pivot, mid),
+                ]
+            }
+            Self::AssignMedian => assign(at_position((domain.size() - 1) / 2)),
+            Self::AssignRandom { seed } => {
+                let random = splitmix64(seed.wrapping_add(n_branches));
+                assign(at_position(random % domain.size()))
+            }
+            Self::Custom(f) => {
+                let value = f(pivot, domain);
+                assign(if domain.contains(value) {
+                    value
+                } else {
+                    domain.min()
+                })
+            }
+        }
+    }
+}
 
-        SplitOnUnassigned {
-            branch: Some((space, pivot, mid, true)),
+impl core::fmt::Debug for ValueSelection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AssignMin => write!(f, "AssignMin"),
+            Self::AssignMax => write!(f, "AssignMax"),
+            Self::SplitLower => write!(f, "SplitLower"),
+            Self::SplitUpper => write!(f, "SplitUpper"),
+            Self::AssignMedian => write!(f, "AssignMedian"),
+            Self::AssignRandom { seed } => {
+                f.debug_struct("AssignRandom").field("seed", seed).finish()
+            }
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// Custom branching strategy, to split search spaces into alternatives to explore in order.
+///
+/// Branchers are chained: search only moves on to the next brancher once the previous ones have
+/// nothing left to branch on. Variables still unassigned at the end of the chain are branched on
+/// with the default strategies of the model.
+pub trait Brancher: core::fmt::Debug + Send + Sync {
+    /// List alternatives to explore, or `None` if there is nothing left to branch on.
+    ///
+    /// Each alternative should restrict the space, otherwise search may never terminate.
+    /// Returning an empty list marks the space as failed.
+    fn branch(&self, space: &SpaceView<'_>) -> Option<Vec<Decision>>;
+}
+
+/// Read-only access to the domains of a search space, to make branching decisions.
+#[derive(Clone, Copy, Debug)]
+pub struct SpaceView<'s> {
+    vars: &'s Vars,
+    props: &'s Propagators,
+    weights: &'s [u32],
+    n_branches: u64,
+}
+
+impl<'s> SpaceView<'s> {
+    /// Access domain of a decision variable.
+    #[must_use]
+    pub fn domain(&self, v: VarId) -> Domain<'s> {
+        Domain::new(&self.vars[v])
+    }
+
+    /// Access domain minimum of a view.
+    pub fn min(&self, x: impl View) -> i32 {
+        x.min_raw(self.vars)
+    }
+
+    /// Access domain maximum of a view.
+    pub fn max(&self, x: impl View) -> i32 {
+        x.max_raw(self.vars)
+    }
+
+    /// Determine if the domain of a view is reduced to a single value.
+    pub fn is_assigned(&self, x: impl View) -> bool {
+        x.min_raw(self.vars) == x.max_raw(self.vars)
+    }
+
+    /// List handles to all unassigned decision variables, in creation order.
+    pub fn get_unassigned_vars_iter(&self) -> impl Iterator<Item = VarId> + 's {
+        self.vars.get_unassigned_vars_iter()
+    }
+}
+
+/// Constraint posted to explore a specific alternative when branching.
+pub struct Decision(Box<dyn FnOnce(&mut Propagators) -> PropId + Send>);
+
+impl Decision {
+    /// Explore the alternative where `x == y`.
+    pub fn equals(x: impl View, y: impl View) -> Self {
+        Self(Box::new(move |props| props.equals(x, y)))
+    }
+
+    /// Explore the alternative where `x != y`.
+    pub fn not_equals(x: impl View, y: impl View) -> Self {
+        Self(Box::new(move |props| props.not_equals(x, y)))
+    }
+
+    /// Explore the alternative where `x <= y`.
+    pub fn less_than_or_equals(x: impl View, y: impl View) -> Self {
+        Self(Box::new(move |props| props.less_than_or_equals(x, y)))
+    }
+
+    /// Explore the alternative where `x < y`.
+    pub fn less_than(x: impl View, y: impl View) -> Self {
+        Self(Box::new(move |props| props.less_than(x, y)))
+    }
+
+    /// Explore the alternative where `x >= y`.
+    pub fn greater_than_or_equals(x: impl View, y: impl View) -> Self {
+        Self(Box::new(move |props| props.greater_than_or_equals(x, y)))
+    }
+
+    /// Explore the alternative where `x > y`.
+    pub fn greater_than(x: impl View, y: impl View) -> Self {
+        Self(Box::new(move |props| props.greater_than(x, y)))
+    }
+
+    /// Declare the propagator that enforces this decision.
+    fn post(self, props: &mut Propagators) -> PropId {
+        (self.0)(props)
+    }
+}
+
+impl core::fmt::Debug for Decision {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Decision")
+    }
+}
+
+/// Variable and value selection strategies, applied to a group of decision variables.
+#[derive(Clone, Debug, Default)]
+struct Group {
+    /// Variables to branch on, or all of them if `None`.
+    vars: Option<Vec<VarId>>,
+    variable_selection: VariableSelection,
+    value_selection: ValueSelection,
+}
+
+impl Brancher for Group {
+    fn branch(&self, space: &SpaceView<'_>) -> Option<Vec<Decision>> {
+        let SpaceView { vars, props, .. } = *space;
+
+        let pivot = if let Some(group) = &self.vars {
+            let vs = group.iter().copied().filter(|&v| !vars[v].is_assigned());
+            self.variable_selection
+                .select(vs, vars, props, space.weights)
+        } else {
+            let vs = vars.get_unassigned_vars_iter();
+            self.variable_selection
+                .select(vs, vars, props, space.weights)
+        }?;
+
+        let decisions = self
+            .value_selection
+            .decide(pivot, space.domain(pivot), space.n_branches);
+
+        Some(decisions.into())
+    }
+}
+
+/// Chain of branchers, tried in order, followed by the default strategies of the model.
+#[derive(Clone, Debug, Default)]
+pub struct Branching {
+    branchers: Vec<Arc<dyn Brancher>>,
+    default: Group,
+}
+
+impl Branching {
+    /// Add a group of decision variables, branched on after previous branchers.
+    pub fn push_group(
+        &mut self,
+        vars: Vec<VarId>,
+        variable_selection: VariableSelection,
+        value_selection: ValueSelection,
+    ) {
+        self.push_brancher(Arc::new(Group {
+            vars: Some(vars),
+            variable_selection,
+            value_selection,
+        }));
+    }
+
+    /// Add a brancher, used once previous ones have nothing left to branch on.
+    pub fn push_brancher(&mut self, brancher: Arc<dyn Brancher>) {
+        self.branchers.push(brancher);
+    }
+
+    /// Set the variable selection strategy for variables left unassigned by all branchers.
+    pub fn set_variable_selection(&mut self, selection: VariableSelection) {
+        self.default.variable_selection = selection;
+    }
+
+    /// Set the value selection strategy for variables left unassigned by all branchers.
+    pub fn set_value_selection(&mut self, selection: ValueSelection) {
+        self.default.value_selection = selection;
+    }
+
+    /// Create alternatives with the first brancher that still has something to branch on.
+    pub fn branch(&self, space: Space, weights: &[u32], n_branches: u64) -> Branch {
+        let view = SpaceView {
+            vars: &space.vars,
+            props: &space.props,
+            weights,
+            n_branches,
+        };
+
+        let mut decisions = self
+            .branchers
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(core::iter::once::<&dyn Brancher>(&self.default))
+            .find_map(|brancher| brancher.branch(&view))
+            .unwrap_or_default();
+
+        // Alternatives are popped from the back, so they are stored in reverse order
+        decisions.reverse();
+
+        Branch {
+            space: Some(space),
+            decisions,
         }
-    } else {
-        SplitOnUnassigned { branch: None }
     }
 }
 
 /// Manual state machine until `gen` keyword is available (edition 2024).
-pub struct SplitOnUnassigned {
-    branch: Option<(Space, VarId, i32, bool)>,
+#[derive(Default)]
+pub struct Branch {
+    space: Option<Space>,
+    decisions: Vec<Decision>,
 }
 
-impl Iterator for SplitOnUnassigned {
+impl Iterator for Branch {
     type Item = (Space, PropId);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (space, pivot, mid, is_left) = self.branch.take()?;
+        let decision = self.decisions.pop()?;
 
-        if is_left {
-            // Split the provided space using a new propagator, to explore a specific branch.
-            let mut space_branch_left = space.clone();
-            let p = space_branch_left.props.less_than_or_equals(pivot, mid);
+        // Last alternative can take ownership of the space, others explore a copy of it
+        let mut space = if self.decisions.is_empty() {
+            self.space.take()?
+        } else {
+            self.space.clone()?
+        };
 
-            self.branch = Some((space, pivot, mid, false));
+        // Split the provided space using a new propagator, to explore a specific branch
+        let p = decision.post(&mut space.props);
 
-            Some((space_branch_left, p))
-        } else {
-            let mut space_branch_right = space;
-            let p = space_branch_right.props.greater_than(pivot, mid);
-            Some((space_branch_right, p))
-        }
+        Some((space, p))
     }
 }
+
+/// Mix bits of the input, to derive pseudo-random numbers from a counter.
+pub const fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
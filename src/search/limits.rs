@@ -0,0 +1,65 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use std::sync::Arc;
+
+/// Budget after which search stops, even if the search tree was not fully explored.
+///
+/// Limits left to `None` are not enforced.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SearchLimits {
+    /// Wall-clock duration, measured from the start of search.
+    pub time: Option<Duration>,
+
+    /// Number of nodes explored, where each node is a space propagated after a branching decision.
+    pub nodes: Option<u64>,
+
+    /// Number of failed spaces.
+    pub failures: Option<u64>,
+
+    /// Number of solutions yielded.
+    pub solutions: Option<u64>,
+}
+
+/// Shared handle to stop a running search from another thread.
+///
+/// Clones refer to the same underlying flag. Search checks it between nodes, and stops promptly
+/// once it is set, as if a limit had been reached.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request all searches using this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Determine if cancellation was requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Progress of search, in terms of the guarantees it can offer on its solutions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SearchStatus {
+    /// Search tree was fully explored, and at least one solution was found.
+    ///
+    /// When optimizing, the last solution is optimal. When enumerating, all solutions were found.
+    Optimal,
+
+    /// At least one solution was found, but the search tree was not fully explored.
+    Feasible,
+
+    /// Search tree was fully explored, without finding any solution.
+    Infeasible,
+
+    /// No solution was found, and the search tree was not fully explored.
+    Unknown,
+}
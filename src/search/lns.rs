@@ -0,0 +1,237 @@
+use core::fmt::Debug;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::props::Propagators;
+use crate::solution::Solution;
+use crate::vars::{VarId, Vars};
+
+use super::branch::splitmix64;
+use super::mode::{Enumerate, Minimize};
+use super::{search, CancellationToken, Config, Report, SearchLimits, SearchStatus, Statistics};
+
+/// Large neighborhood search parameters, to improve on an incumbent solution.
+///
+/// Each iteration fixes some variables to their value in the incumbent solution,
+/// and searches the remaining ones for an assignment with a lower objective value.
+/// Search limits of the model apply to the whole run, instead of each neighborhood.
+#[derive(Clone, Debug)]
+pub struct Lns {
+    /// Strategy to select which variables keep their incumbent value.
+    pub neighborhood: Arc<dyn Neighborhood>,
+
+    /// Number of failures after which the search of a neighborhood is abandoned.
+    pub failures: u64,
+
+    /// Number of neighborhoods to explore, unbounded if `None`.
+    ///
+    /// Without this or a search limit, search only stops once the incumbent is proven optimal,
+    /// which requires a neighborhood that leaves all variables free.
+    pub iterations: Option<u64>,
+
+    /// Seed from which the pseudo-random numbers passed to the neighborhood are derived.
+    pub seed: u64,
+}
+
+impl Lns {
+    /// Explore neighborhoods of the provided kind, with a default failure limit of 100 each.
+    #[must_use]
+    pub fn new(neighborhood: impl Neighborhood + 'static) -> Self {
+        Self {
+            neighborhood: Arc::new(neighborhood),
+            failures: 100,
+            iterations: None,
+            seed: 0,
+        }
+    }
+}
+
+/// Neighborhood selector, to implement custom large neighborhood search strategies.
+pub trait Neighborhood: Debug + Send + Sync {
+    /// Select variables to fix to their value in the incumbent solution.
+    ///
+    /// The provided pseudo-random number differs for every iteration.
+    fn select(&self, incumbent: &Solution, random: u64) -> Vec<VarId>;
+}
+
+/// Fix all variables of a list but a few, picked uniformly at random.
+#[derive(Clone, Debug)]
+pub struct RandomNeighborhood {
+    vs: Vec<VarId>,
+    n_free: usize,
+}
+
+impl RandomNeighborhood {
+    /// Leave the provided number of variables free in each neighborhood.
+    #[must_use]
+    pub fn new(vs: &[VarId], n_free: usize) -> Self {
+        Self {
+            vs: vs.to_vec(),
+            n_free,
+        }
+    }
+}
+
+impl Neighborhood for RandomNeighborhood {
+    fn select(&self, _: &Solution, random: u64) -> Vec<VarId> {
+        let mut vs = self.vs.clone();
+        let n_free = self.n_free.min(vs.len());
+        let mut random = random;
+
+        // Partial Fisher-Yates shuffle, to move free variables to the front
+        for i in 0..n_free {
+            let n_left = (vs.len() - i) as u64;
+
+            // Remainder is lower than the number of variables left, which fits in usize
+            #[allow(clippy::cast_possible_truncation)]
+            let j = i + (random % n_left) as usize;
+
+            vs.swap(i, j);
+            random = splitmix64(random);
+        }
+
+        vs.split_off(n_free)
+    }
+}
+
+/// Find an initial solution, and improve it by searching neighborhoods of the incumbent.
+///
+/// The objective is expected to be a decision variable, to read its value from solutions.
+pub fn lns(
+    vars: &Vars,
+    props: &Propagators,
+    objective: VarId,
+    config: &Config,
+    lns: &Lns,
+) -> Report {
+    // Search budget is shared between the initial solution and all neighborhoods
+    let start = Instant::now();
+    let limits = config.limits;
+
+    let mut statistics = Statistics {
+        propagations: vec![0; props.get_prop_count()],
+        ..Statistics::default()
+    };
+
+    // Initial solution does not need to be good, only feasible
+    let initial_config = Config {
+        limits: SearchLimits {
+            solutions: None,
+            ..limits
+        },
+        ..config.clone()
+    };
+
+    let mut solutions = search(
+        vars.clone(),
+        props.clone(),
+        Box::new(Enumerate),
+        initial_config,
+    );
+    let initial_opt = solutions.next();
+    statistics.merge(&solutions.statistics());
+
+    let Some(mut incumbent) = initial_opt else {
+        statistics.time = start.elapsed();
+
+        return Report {
+            solution: None,
+            status: solutions.status(),
+            statistics,
+        };
+    };
+
+    let mut status = SearchStatus::Feasible;
+    let mut iteration = 0;
+
+    while !is_over(config, lns, &statistics, iteration, start) {
+        let random = splitmix64(lns.seed.wrapping_add(iteration));
+        let fixed = lns.neighborhood.select(&incumbent, random);
+
+        // Neighborhood only contains assignments that improve on the incumbent
+        let mut neighborhood = props.clone();
+        for &v in &fixed {
+            let _p = neighborhood.equals(v, incumbent[v]);
+        }
+        let _p = neighborhood.less_than(objective, incumbent[objective]);
+
+        // Each neighborhood gets its own failure limit, and what is left of the global budget
+        let neighborhood_config = Config {
+            limits: SearchLimits {
+                time: limits.time.map(|time| time.saturating_sub(start.elapsed())),
+                nodes: limits
+                    .nodes
+                    .map(|nodes| nodes.saturating_sub(statistics.nodes)),
+                failures: Some(limits.failures.map_or(lns.failures, |failures| {
+                    failures
+                        .saturating_sub(statistics.failures)
+                        .min(lns.failures)
+                })),
+                solutions: limits
+                    .solutions
+                    .map(|solutions| solutions.saturating_sub(statistics.solutions)),
+            },
+            ..config.clone()
+        };
+
+        let mut solutions = search(
+            vars.clone(),
+            neighborhood,
+            Box::new(Minimize::new(objective)),
+            neighborhood_config,
+        );
+
+        if let Some(solution) = solutions.by_ref().last() {
+            incumbent = solution;
+        }
+
+        statistics.merge(&solutions.statistics());
+        iteration += 1;
+
+        // Exhausting a neighborhood without fixed variables proves the incumbent is optimal
+        let is_complete = matches!(
+            solutions.status(),
+            SearchStatus::Optimal | SearchStatus::Infeasible
+        );
+
+        if fixed.is_empty() && is_complete {
+            status = SearchStatus::Optimal;
+            break;
+        }
+    }
+
+    statistics.time = start.elapsed();
+
+    Report {
+        solution: Some(incumbent),
+        status,
+        statistics,
+    }
+}
+
+/// Determine if no further neighborhood should be explored.
+fn is_over(
+    config: &Config,
+    lns: &Lns,
+    statistics: &Statistics,
+    iteration: u64,
+    start: Instant,
+) -> bool {
+    let limits = &config.limits;
+
+    config
+        .cancellation_opt
+        .as_ref()
+        .is_some_and(CancellationToken::is_cancelled)
+        || lns
+            .iterations
+            .is_some_and(|iterations| iteration >= iterations)
+        || limits.nodes.is_some_and(|nodes| statistics.nodes >= nodes)
+        || limits
+            .failures
+            .is_some_and(|failures| statistics.failures >= failures)
+        || limits
+            .solutions
+            .is_some_and(|solutions| statistics.solutions >= solutions)
+        || limits.time.is_some_and(|time| start.elapsed() >= time)
+}
@@ -2,17 +2,32 @@ pub mod mode;
 
 mod agenda;
 mod branch;
+mod limits;
+mod lns;
+mod parallel;
+mod restart;
+mod stats;
 
 use core::mem::replace;
+use std::time::Instant;
 
-use crate::props::Propagators;
+use crate::props::{PropId, Propagators};
 use crate::solution::Solution;
 use crate::vars::Vars;
 use crate::views::Context;
 
 use self::agenda::Agenda;
-use self::branch::{split_on_unassigned, SplitOnUnassigned};
+use self::branch::Branch;
 use self::mode::Mode;
+use self::parallel::{Shared, Threads};
+
+pub use self::branch::{
+    Brancher, Branching, Decision, SpaceView, ValueSelection, ValueSelector, VariableSelection,
+};
+pub use self::limits::{CancellationToken, SearchLimits, SearchStatus};
+pub use self::lns::{lns, Lns, Neighborhood, RandomNeighborhood};
+pub use self::restart::RestartSchedule;
+pub use self::stats::{Report, Statistics};
 
 /// Data required to perform search, copied on branch and discarded on failure.
 #[derive(Clone, Debug)]
@@ -21,77 +36,418 @@ pub struct Space {
     pub props: Propagators,
 }
 
+/// Search parameters, shared by all search modes.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub branching: Branching,
+    pub limits: SearchLimits,
+    pub cancellation_opt: Option<CancellationToken>,
+    pub restarts_opt: Option<RestartSchedule>,
+
+    /// Number of threads sharing the search tree, search is sequential below two.
+    pub threads: usize,
+}
+
 /// Perform search, iterating over assignments that satisfy all constraints.
-pub fn search<M: Mode>(vars: Vars, props: Propagators, mode: M) -> Search<M> {
+pub fn search(vars: Vars, props: Propagators, mode: Box<dyn Mode>, config: Config) -> Solutions {
+    // Search budget starts being consumed before initial propagation
+    let start = Instant::now();
+    let limit_opt = config.limits.solutions;
+
+    // Branching decisions are posted as extra propagators, which do not get tracked
+    let mut statistics = Statistics {
+        propagations: vec![0; props.get_prop_count()],
+        ..Statistics::default()
+    };
+
     // Schedule all propagators during initial propagation step
     let agenda = Agenda::with_props(props.get_prop_ids_iter());
 
     // Propagate constraints until search is stalled or a solution is found
-    let Some((is_stalled, space)) = propagate(Space { vars, props }, agenda) else {
-        return Search::Done(None);
+    let search = match propagate(Space { vars, props }, agenda, &mut statistics.propagations) {
+        // Explore space by alternating branching and propagation, with several threads if asked
+        Ok((true, space)) if config.threads > 1 => {
+            Search::Parallel(spawn_threads(space, mode.as_ref(), &config, start))
+        }
+        Ok((true, space)) => Search::Stalled(Box::new(Engine::new(space, mode, config, start))),
+        Ok((false, space)) => Search::Done(Some(space)),
+        Err(_) => Search::Done(None),
     };
 
-    // Explore space by alternating branching and propagation
-    if is_stalled {
-        Search::Stalled(Engine::new(space, mode))
-    } else {
-        Search::Done(Some(space))
+    Solutions {
+        search,
+        limit_opt,
+        statistics,
+        start,
+        status_opt: None,
     }
 }
 
-/// Manual state machine until `gen` keyword is available (edition 2024).
-pub enum Search<M> {
-    Stalled(Engine<M>),
-    Done(Option<Space>),
+/// Split the root space between threads, which share nodes whenever one of them runs out.
+fn spawn_threads(space: Space, mode: &dyn Mode, config: &Config, start: Instant) -> Threads {
+    let n_props = space.props.get_prop_count();
+
+    // Alternatives of the root space are the first nodes to be shared
+    let nodes = config
+        .branching
+        .branch(space, &vec![1; n_props], 0)
+        .collect();
+
+    let engines = (0..config.threads)
+        .map(|_| Engine::idle(n_props, mode.fork(), config.clone(), start))
+        .collect();
+
+    Threads::steal(nodes, engines)
+}
+
+/// Run a search for each member concurrently, until one of them explores its whole search tree.
+///
+/// Members are expected to be formulations of the same problem, sharing the mode's bound.
+pub fn portfolio(members: Vec<(Vars, Propagators, Config)>, mode: &dyn Mode) -> Solutions {
+    let start = Instant::now();
+
+    // Propagators of the first member get tracked, others may differ in their formulation
+    let n_props = members
+        .first()
+        .map_or(0, |(_, props, _)| props.get_prop_count());
+
+    let mut statistics = Statistics {
+        propagations: vec![0; n_props],
+        ..Statistics::default()
+    };
+
+    let mut engines = Vec::new();
+    let mut search_opt = None;
+
+    for (vars, props, config) in members {
+        let agenda = Agenda::with_props(props.get_prop_ids_iter());
+
+        // Outcome of initial propagation settles search for all members
+        match propagate(Space { vars, props }, agenda, &mut statistics.propagations) {
+            Ok((true, space)) => engines.push(Engine::new(space, mode.fork(), config, start)),
+            Ok((false, space)) => search_opt = Some(Search::Done(Some(space))),
+            Err(_) => search_opt = Some(Search::Done(None)),
+        }
+
+        if search_opt.is_some() {
+            break;
+        }
+    }
+
+    Solutions {
+        search: search_opt.unwrap_or_else(|| Search::Parallel(Threads::portfolio(engines))),
+        limit_opt: None,
+        statistics,
+        start,
+        status_opt: None,
+    }
+}
+
+/// Iterator over assignments that satisfy all constraints, in the order search finds them.
+///
+/// Once exhausted, it reports whether search explored the whole search tree or hit a limit.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Solutions {
+    search: Search,
+    limit_opt: Option<u64>,
+    statistics: Statistics,
+    start: Instant,
+
+    /// Final status, set once search is over.
+    status_opt: Option<SearchStatus>,
+}
+
+impl Solutions {
+    /// Progress of search, in terms of the guarantees it can offer on yielded solutions.
+    #[must_use]
+    pub fn status(&self) -> SearchStatus {
+        self.status_opt.unwrap_or(if self.statistics.solutions > 0 {
+            SearchStatus::Feasible
+        } else {
+            SearchStatus::Unknown
+        })
+    }
+
+    /// Counters collected so far, wall-clock time stops increasing once search is over.
+    #[must_use]
+    pub fn statistics(&self) -> Statistics {
+        let mut statistics = self.statistics.clone();
+
+        if self.status_opt.is_none() {
+            statistics.time = self.start.elapsed();
+        }
+
+        statistics
+    }
+
+    /// Consume search up to the provided solution, and report on how it went.
+    pub(crate) fn into_report(self, solution: Option<Solution>) -> Report {
+        Report {
+            solution,
+            status: self.status(),
+            statistics: self.statistics(),
+        }
+    }
+
+    /// Record that search is over, whether it explored the whole tree or not.
+    fn finish(&mut self, is_complete: bool) {
+        let status = match (is_complete, self.statistics.solutions > 0) {
+            (true, true) => SearchStatus::Optimal,
+            (true, false) => SearchStatus::Infeasible,
+            (false, true) => SearchStatus::Feasible,
+            (false, false) => SearchStatus::Unknown,
+        };
+
+        self.status_opt = Some(status);
+        self.statistics.time = self.start.elapsed();
+    }
 }
 
-impl<M: Mode> Iterator for Search<M> {
+impl Iterator for Solutions {
     type Item = Solution;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self {
-            Self::Stalled(engine) => engine.next(),
-            Self::Done(space_opt) => space_opt.take().map(|space| space.vars.into_solution()),
+        // Search is not resumed once over
+        if self.status_opt.is_some() {
+            return None;
+        }
+
+        // Stop before looking for another solution once enough were found
+        if self
+            .limit_opt
+            .is_some_and(|limit| self.statistics.solutions >= limit)
+        {
+            self.finish(false);
+            return None;
+        }
+
+        let solution_opt = match &mut self.search {
+            Search::Stalled(engine) => engine.next_solution(&mut self.statistics),
+            Search::Parallel(threads) => threads.next_solution(&mut self.statistics),
+            Search::Done(space_opt) => space_opt.take().map(|space| space.vars.into_solution()),
+        };
+
+        if solution_opt.is_some() {
+            self.statistics.solutions += 1;
+        } else {
+            let is_complete = match &self.search {
+                Search::Stalled(engine) => !engine.is_interrupted,
+                Search::Parallel(threads) => threads.is_complete(),
+                Search::Done(_) => true,
+            };
+
+            self.finish(is_complete);
         }
+
+        solution_opt
     }
 }
 
 /// Manual state machine until `gen` keyword is available (edition 2024).
-pub struct Engine<M> {
-    branch_iter: SplitOnUnassigned,
-    stack: Vec<SplitOnUnassigned>,
-    mode: M,
+enum Search {
+    Stalled(Box<Engine>),
+    Parallel(Threads),
+    Done(Option<Space>),
+}
+
+/// Manual state machine until `gen` keyword is available (edition 2024).
+struct Engine {
+    branch_iter: Branch,
+    stack: Vec<Branch>,
+    mode: Box<dyn Mode>,
+    branching: Branching,
+    limits: SearchLimits,
+    cancellation_opt: Option<CancellationToken>,
+
+    /// Failure count of each propagator of the model, starting at one, to guide branching.
+    weights: Vec<u32>,
+
+    /// Number of spaces branched on so far, to seed randomized strategies.
+    n_branches: u64,
+
+    /// Time at which search started, to enforce its time limit.
+    start: Instant,
+
+    /// Copy of the root space and schedule of failure limits, if search restarts.
+    restarts_opt: Option<(Space, RestartSchedule)>,
+
+    /// Number of failures since the last restart.
+    n_failures_run: u64,
+
+    /// Search was stopped by a limit, before exploring the whole search tree.
+    is_interrupted: bool,
+
+    /// Handles to other threads, when searching in parallel.
+    shared_opt: Option<Shared>,
 }
 
-impl<M> Engine<M> {
-    fn new(space: Space, mode: M) -> Self {
+impl Engine {
+    fn new(space: Space, mode: Box<dyn Mode>, config: Config, start: Instant) -> Self {
+        // Root space is kept around to restart search from it
+        let restarts_opt = config
+            .restarts_opt
+            .map(|schedule| (space.clone(), schedule));
+
+        let mut engine = Self::idle(space.props.get_prop_count(), mode, config, start);
+
         // Preserve a trail of copies to allow backtracking on failed spaces
+        engine.branch_iter = engine.branching.branch(space, &engine.weights, 0);
+        engine.restarts_opt = restarts_opt;
+
+        engine
+    }
+
+    /// Prepare an engine without any space to explore, which parallel search will provide.
+    fn idle(n_props: usize, mode: Box<dyn Mode>, config: Config, start: Instant) -> Self {
         Self {
-            branch_iter: split_on_unassigned(space),
+            branch_iter: Branch::default(),
             stack: Vec::new(),
             mode,
+            branching: config.branching,
+            limits: config.limits,
+            cancellation_opt: config.cancellation_opt,
+
+            // Branching decisions are posted as extra propagators, which do not get weighted
+            weights: vec![1; n_props],
+
+            n_branches: 1,
+            start,
+            restarts_opt: None,
+            n_failures_run: 0,
+            is_interrupted: false,
+            shared_opt: None,
         }
     }
-}
 
-impl<M: Mode> Iterator for Engine<M> {
-    type Item = Solution;
+    /// Determine if the current run exhausted its failure limit, and search may restart.
+    fn is_restart_due(&self, statistics: &Statistics) -> bool {
+        // Restarting would yield the same solutions again, unless the mode prevents it
+        let is_allowed = self.mode.keeps_bound() || statistics.solutions == 0;
 
-    fn next(&mut self) -> Option<Self::Item> {
+        self.restarts_opt.as_ref().is_some_and(|(_, schedule)| {
+            is_allowed && self.n_failures_run >= schedule.get_failure_limit(statistics.restarts)
+        })
+    }
+
+    /// Discard the current search tree, and branch on the root space again.
+    fn restart(&mut self, statistics: &mut Statistics) {
+        let Some((root, _)) = &self.restarts_opt else {
+            return;
+        };
+
+        // Failure weights and the mode's internal state are kept, to guide the next run
+        self.branch_iter = self
+            .branching
+            .branch(root.clone(), &self.weights, self.n_branches);
+        self.stack.clear();
+        self.n_branches += 1;
+        self.n_failures_run = 0;
+
+        statistics.restarts += 1;
+    }
+
+    /// Determine if any of the search limits has been reached, or if search was cancelled.
+    fn is_limit_reached(&self, statistics: &Statistics) -> bool {
+        let limits = &self.limits;
+
+        // Threads sharing a search tree also share its budget
+        let (n_nodes, n_failures) = match &self.shared_opt {
+            Some(shared) if shared.pool.is_stealing() => shared.pool.get_counts(),
+            _ => (statistics.nodes, statistics.failures),
+        };
+
+        self.cancellation_opt
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+            || self
+                .shared_opt
+                .as_ref()
+                .is_some_and(|shared| shared.pool.is_stopped())
+            || limits.nodes.is_some_and(|nodes| n_nodes >= nodes)
+            || limits
+                .failures
+                .is_some_and(|failures| n_failures >= failures)
+            || limits.time.is_some_and(|time| self.start.elapsed() >= time)
+    }
+
+    /// Give the oldest alternatives left to idle threads, which likely hold the largest subtrees.
+    fn share_nodes(&mut self) {
+        let Some(shared) = &self.shared_opt else {
+            return;
+        };
+
+        while !self.stack.is_empty() {
+            let nodes: Vec<_> = self.stack.remove(0).collect();
+
+            if !nodes.is_empty() {
+                shared.pool.share(nodes);
+                return;
+            }
+        }
+    }
+
+    /// Explore search tree until the next solution, updating search statistics along the way.
+    fn next_solution(&mut self, statistics: &mut Statistics) -> Option<Solution> {
         loop {
-            while let Some((mut space, p)) = self.branch_iter.next() {
-                // Schedule propagator triggered by the branch
-                let agenda =
-                    Agenda::with_props(self.mode.on_branch(&mut space).chain(core::iter::once(p)));
+            // Interrupted search cannot be resumed
+            if self.is_interrupted || self.is_limit_reached(statistics) {
+                self.is_interrupted = true;
+                return None;
+            }
+
+            if self.is_restart_due(statistics) {
+                self.restart(statistics);
+            }
+
+            if self
+                .shared_opt
+                .as_ref()
+                .is_some_and(|shared| shared.pool.is_starving())
+            {
+                self.share_nodes();
+            }
+
+            let (mut space, p) = if let Some(node) = self.branch_iter.next() {
+                node
+            } else if let Some(branch) = self.stack.pop() {
+                self.branch_iter = branch;
+                continue;
+            } else {
+                // Other threads may still have nodes to share, once this one runs out of them
+                let shared = self.shared_opt.as_ref()?;
+
+                let Some(node) = shared.pool.steal() else {
+                    self.is_interrupted = shared.pool.is_stopped();
+                    return None;
+                };
+
+                node
+            };
+
+            statistics.nodes += 1;
+
+            if let Some(shared) = &self.shared_opt {
+                shared.pool.on_node();
+            }
+
+            // Schedule propagator triggered by the branch
+            let agenda = Agenda::with_props(self.mode.on_branch(&mut space).into_iter().chain([p]));
 
-                // Failed spaces are discarded, fixed points get explored further (depth-first search)
-                if let Some((is_stalled, space)) = propagate(space, agenda) {
+            // Failed spaces are discarded, fixed points get explored further (depth-first search)
+            match propagate(space, agenda, &mut statistics.propagations) {
+                Ok((is_stalled, space)) => {
                     if is_stalled {
                         // Branch on new space, to explore it further
-                        let parent = replace(&mut self.branch_iter, split_on_unassigned(space));
+                        let child = self.branching.branch(space, &self.weights, self.n_branches);
+                        let parent = replace(&mut self.branch_iter, child);
+                        self.n_branches += 1;
 
                         // Save where search will resume if sub-space gets failed
                         self.stack.push(parent);
+                        statistics.max_depth = statistics.max_depth.max(self.stack.len());
+                    } else if let Some(shared) = &self.shared_opt {
+                        // Threads submit solutions as they go, other threads may have found better
+                        shared.submit(self.mode.as_mut(), space.vars);
                     } else {
                         // Mode object may update its internal state when new solutions are found
                         self.mode.on_solution(&space.vars);
@@ -100,15 +456,33 @@ impl<M: Mode> Iterator for Engine<M> {
                         return Some(space.vars.into_solution());
                     }
                 }
-            }
+                Err(p) => {
+                    statistics.failures += 1;
+                    self.n_failures_run += 1;
 
-            self.branch_iter = self.stack.pop()?;
+                    if let Some(shared) = &self.shared_opt {
+                        shared.pool.on_failure();
+                    }
+
+                    // Constraints that fail often are more likely to be hard to satisfy
+                    if let Some(weight) = self.weights.get_mut(p.0) {
+                        *weight = weight.saturating_add(1);
+                    }
+                }
+            }
         }
     }
 }
 
 /// Apply scheduled propagators, pruning domains until space is failed, stalled, or assigned.
-fn propagate(mut space: Space, mut agenda: Agenda) -> Option<(bool, Space)> {
+///
+/// Invocations of each propagator of the model are counted, branching decisions are ignored.
+/// On failure, the handle to the propagator that failed the space is returned.
+fn propagate(
+    mut space: Space,
+    mut agenda: Agenda,
+    propagations: &mut [u64],
+) -> Result<(bool, Space), PropId> {
     // Track which domains got updated, to schedule next propagators in batch
     let mut events = Vec::new();
 
@@ -120,22 +494,27 @@ fn propagate(mut space: Space, mut agenda: Agenda) -> Option<(bool, Space)> {
         // Wrap engine objects before passing them to user-controlled propagation logic
         let mut ctx = Context::new(&mut space.vars, &mut events);
 
+        if let Some(n) = propagations.get_mut(p.0) {
+            *n += 1;
+        }
+
         // Prune decision variable domains to enforce constraints
-        prop.prune(&mut ctx)?;
+        prop.prune(&mut ctx).ok_or(p)?;
+
+        // Update dependencies of propagators that changed which variables they watch
+        for (from, to) in ctx.into_trigger_moves() {
+            space.props.move_trigger_var(p, from, to);
+        }
 
         // Schedule propagators that depend on changed variables
         #[allow(clippy::iter_with_drain)]
         for v in events.drain(..) {
-            for p in space.props.on_bound_change(v) {
+            for p in space.props.on_domain_change(v) {
                 agenda.schedule(p);
             }
         }
-
-        // Search is over once all decision variables have been assigned
-        if space.vars.is_assigned_all() {
-            return Some((false, space));
-        }
     }
 
-    Some((true, space))
+    // Search is over once all decision variables have been assigned, and all propagators agree
+    Ok((!space.vars.is_assigned_all(), space))
 }
@@ -1,51 +1,89 @@
+use core::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
 use crate::props::PropId;
 use crate::search::Space;
 use crate::vars::Vars;
 use crate::views::View;
 
 /// Control search behavior when a solution is found.
-pub trait Mode: core::fmt::Debug {
-    /// List propagators to be scheduled on after branch.
-    fn on_branch(&self, _: &mut Space) -> impl Iterator<Item = PropId> {
-        core::iter::empty()
+pub trait Mode: core::fmt::Debug + Send {
+    /// Declare propagator to be scheduled after branch, if any.
+    fn on_branch(&self, _: &mut Space) -> Option<PropId> {
+        None
     }
 
     /// Update internal state when new solution is found.
     fn on_solution(&mut self, _vars: &Vars) {}
+
+    /// Determine if internal state prevents solutions from being found again after a restart.
+    fn keeps_bound(&self) -> bool {
+        false
+    }
+
+    /// Determine if a solution is still worth yielding, once found by one of several threads.
+    fn is_improving(&self, _vars: &Vars) -> bool {
+        true
+    }
+
+    /// Copy mode for another thread, sharing internal state with this one.
+    fn fork(&self) -> Box<dyn Mode>;
 }
 
 /// Enumerate assignments that satisfy all constraints.
 #[derive(Debug)]
 pub struct Enumerate;
 
-impl Mode for Enumerate {}
+impl Mode for Enumerate {
+    fn fork(&self) -> Box<dyn Mode> {
+        Box::new(Self)
+    }
+}
 
 /// Enumerate assignments that satisfy all constraints, and gradually lower objective expression.
-#[derive(Debug)]
+///
+/// Copies share the lowest objective value found so far, to prune search in all threads.
+#[derive(Clone, Debug)]
 pub struct Minimize<V> {
     objective: V,
-    minimum_opt: Option<i32>,
+
+    /// Lowest objective value found so far, `i64::MAX` until the first solution.
+    minimum: Arc<AtomicI64>,
 }
 
 impl<V: View> Minimize<V> {
-    pub const fn new(objective: V) -> Self {
+    pub fn new(objective: V) -> Self {
         Self {
             objective,
-            minimum_opt: None,
+            minimum: Arc::new(AtomicI64::new(i64::MAX)),
         }
     }
 }
 
 impl<V: View> Mode for Minimize<V> {
-    fn on_branch(&self, space: &mut Space) -> impl Iterator<Item = PropId> {
+    fn on_branch(&self, space: &mut Space) -> Option<PropId> {
         // Prune assignments that cannot lower objective expression
-        self.minimum_opt
-            .map(|minimum| space.props.less_than(self.objective, minimum))
-            .into_iter()
+        let minimum = i32::try_from(self.minimum.load(Ordering::Relaxed)).ok()?;
+
+        Some(space.props.less_than(self.objective, minimum))
     }
 
     fn on_solution(&mut self, vars: &Vars) {
-        // New objective value is necessarily lower than previous lowest
-        self.minimum_opt = Some(self.objective.min_raw(vars));
+        // New objective value is lower than previous lowest, unless another thread found better
+        let _minimum = self
+            .minimum
+            .fetch_min(self.objective.min_raw(vars).into(), Ordering::Relaxed);
+    }
+
+    fn keeps_bound(&self) -> bool {
+        true
+    }
+
+    fn is_improving(&self, vars: &Vars) -> bool {
+        i64::from(self.objective.min_raw(vars)) < self.minimum.load(Ordering::Relaxed)
+    }
+
+    fn fork(&self) -> Box<dyn Mode> {
+        Box::new(self.clone())
     }
 }
@@ -0,0 +1,297 @@
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use crate::props::PropId;
+use crate::solution::Solution;
+use crate::vars::Vars;
+
+use super::mode::Mode;
+use super::{Engine, Space, Statistics};
+
+/// Message sent by search threads to the thread iterating over solutions.
+enum Message {
+    Solution(Solution),
+
+    /// Thread stopped searching, after exploring all the nodes it was given or not.
+    Done {
+        statistics: Statistics,
+        is_complete: bool,
+    },
+}
+
+/// State shared by threads searching in parallel.
+pub struct Pool {
+    /// Spaces waiting to be explored, along with the propagator of the decision that created them.
+    nodes: Mutex<Vec<(Space, PropId)>>,
+
+    /// Wake idle threads up when nodes get shared, or when search is over.
+    on_change: Condvar,
+
+    /// Number of threads sharing nodes, zero for portfolios where each explores its own tree.
+    n_stealing: usize,
+
+    /// Number of threads waiting for nodes, updated under lock of the nodes.
+    n_idle: AtomicUsize,
+
+    /// Number of nodes waiting for threads, updated under lock of the nodes.
+    n_waiting: AtomicUsize,
+
+    /// Search counters of all threads, to enforce limits globally.
+    n_nodes: AtomicU64,
+    n_failures: AtomicU64,
+
+    /// Search was interrupted by the thread iterating over solutions, or a portfolio member.
+    is_stopped: AtomicBool,
+
+    /// Serialize solutions, so they are received in the order the mode accepted them.
+    incumbent: Mutex<()>,
+}
+
+impl Pool {
+    fn new(nodes: Vec<(Space, PropId)>, n_stealing: usize) -> Self {
+        Self {
+            n_waiting: AtomicUsize::new(nodes.len()),
+            nodes: Mutex::new(nodes),
+            on_change: Condvar::new(),
+            n_stealing,
+            n_idle: AtomicUsize::new(0),
+            n_nodes: AtomicU64::new(0),
+            n_failures: AtomicU64::new(0),
+            is_stopped: AtomicBool::new(false),
+            incumbent: Mutex::new(()),
+        }
+    }
+
+    /// Determine if threads share nodes, rather than each exploring its own search tree.
+    pub const fn is_stealing(&self) -> bool {
+        self.n_stealing > 0
+    }
+
+    /// Determine if some threads are waiting for more nodes than there are available.
+    pub fn is_starving(&self) -> bool {
+        self.n_idle.load(Ordering::Relaxed) > self.n_waiting.load(Ordering::Relaxed)
+    }
+
+    /// Determine if search should stop, regardless of nodes left to explore.
+    pub fn is_stopped(&self) -> bool {
+        self.is_stopped.load(Ordering::Relaxed)
+    }
+
+    /// Stop all threads, including idle ones.
+    fn stop(&self) {
+        let _nodes = self.nodes.lock().unwrap_or_else(PoisonError::into_inner);
+
+        self.is_stopped.store(true, Ordering::Relaxed);
+        self.on_change.notify_all();
+    }
+
+    /// Make nodes available to idle threads.
+    pub fn share(&self, shared: Vec<(Space, PropId)>) {
+        let mut nodes = self.nodes.lock().unwrap_or_else(PoisonError::into_inner);
+
+        nodes.extend(shared);
+        self.n_waiting.store(nodes.len(), Ordering::Relaxed);
+        drop(nodes);
+
+        self.on_change.notify_all();
+    }
+
+    /// Wait until a node is available, or until search is over.
+    pub fn steal(&self) -> Option<(Space, PropId)> {
+        // Portfolio members do not share nodes, running out of them means their search is over
+        if !self.is_stealing() {
+            return None;
+        }
+
+        let mut nodes = self.nodes.lock().unwrap_or_else(PoisonError::into_inner);
+        let _n_idle = self.n_idle.fetch_add(1, Ordering::Relaxed);
+
+        loop {
+            if self.is_stopped() {
+                return None;
+            }
+
+            if let Some(node) = nodes.pop() {
+                let _n_idle = self.n_idle.fetch_sub(1, Ordering::Relaxed);
+                self.n_waiting.store(nodes.len(), Ordering::Relaxed);
+
+                return Some(node);
+            }
+
+            // Search tree is fully explored once all threads are idle, with no node left
+            if self.n_idle.load(Ordering::Relaxed) == self.n_stealing {
+                self.on_change.notify_all();
+                return None;
+            }
+
+            nodes = self
+                .on_change
+                .wait(nodes)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Count a node explored by one of the threads.
+    pub fn on_node(&self) {
+        let _n_nodes = self.n_nodes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a failed space reached by one of the threads.
+    pub fn on_failure(&self) {
+        let _n_failures = self.n_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of nodes explored and failures reached by all threads so far.
+    pub fn get_counts(&self) -> (u64, u64) {
+        let n_nodes = self.n_nodes.load(Ordering::Relaxed);
+        let n_failures = self.n_failures.load(Ordering::Relaxed);
+
+        (n_nodes, n_failures)
+    }
+}
+
+/// Handles a search thread uses to communicate with the others.
+pub struct Shared {
+    pub pool: Arc<Pool>,
+    sender: Sender<Message>,
+}
+
+impl Shared {
+    /// Send solution to the thread iterating over them, unless another thread found a better one.
+    pub fn submit(&self, mode: &mut dyn Mode, vars: Vars) {
+        let _incumbent = self
+            .pool
+            .incumbent
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if mode.is_improving(&vars) {
+            mode.on_solution(&vars);
+
+            let _r = self.sender.send(Message::Solution(vars.into_solution()));
+        }
+    }
+}
+
+/// Threads searching in parallel, as seen from the thread iterating over solutions.
+pub struct Threads {
+    receiver: Receiver<Message>,
+    pool: Arc<Pool>,
+    handles: Vec<JoinHandle<()>>,
+
+    /// Number of threads that have not reported being done yet.
+    n_running: usize,
+
+    /// Number of threads that explored all the nodes they were given.
+    n_complete: usize,
+}
+
+impl Threads {
+    /// Explore the provided nodes with several threads, which share them whenever one runs out.
+    pub fn steal(nodes: Vec<(Space, PropId)>, engines: Vec<Engine>) -> Self {
+        Self::spawn(Pool::new(nodes, engines.len()), engines)
+    }
+
+    /// Run independent searches concurrently, each thread exploring its own search tree.
+    pub fn portfolio(engines: Vec<Engine>) -> Self {
+        Self::spawn(Pool::new(Vec::new(), 0), engines)
+    }
+
+    fn spawn(pool: Pool, engines: Vec<Engine>) -> Self {
+        let pool = Arc::new(pool);
+        let (sender, receiver) = channel();
+
+        let n_running = engines.len();
+
+        let handles = engines
+            .into_iter()
+            .map(|mut engine| {
+                let pool = Arc::clone(&pool);
+                let sender = sender.clone();
+
+                engine.shared_opt = Some(Shared {
+                    pool: Arc::clone(&pool),
+                    sender: sender.clone(),
+                });
+
+                thread::spawn(move || {
+                    let mut statistics = Statistics {
+                        propagations: vec![0; engine.weights.len()],
+                        ..Statistics::default()
+                    };
+
+                    // Solutions are submitted as they are found, engine only returns once done
+                    let _solution = engine.next_solution(&mut statistics);
+                    let is_complete = !engine.is_interrupted;
+
+                    // Nodes left by an interrupted thread cannot be explored by others anymore,
+                    // while the first portfolio member to complete its search settles it
+                    let is_over = if pool.is_stealing() {
+                        !is_complete
+                    } else {
+                        is_complete
+                    };
+
+                    if is_over {
+                        pool.stop();
+                    }
+
+                    let _r = sender.send(Message::Done {
+                        statistics,
+                        is_complete,
+                    });
+                })
+            })
+            .collect();
+
+        Self {
+            receiver,
+            pool,
+            handles,
+            n_running,
+            n_complete: 0,
+        }
+    }
+
+    /// Wait for the next solution, merging statistics of threads that are done in the meantime.
+    pub fn next_solution(&mut self, statistics: &mut Statistics) -> Option<Solution> {
+        while self.n_running > 0 {
+            match self.receiver.recv().ok()? {
+                Message::Solution(solution) => return Some(solution),
+                Message::Done {
+                    statistics: thread_statistics,
+                    is_complete,
+                } => {
+                    statistics.merge(&thread_statistics);
+
+                    self.n_running -= 1;
+                    self.n_complete += usize::from(is_complete);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Determine if the whole search tree was explored, once all threads are done.
+    pub fn is_complete(&self) -> bool {
+        if self.pool.is_stealing() {
+            self.n_complete == self.handles.len()
+        } else {
+            self.n_complete > 0
+        }
+    }
+}
+
+impl Drop for Threads {
+    fn drop(&mut self) {
+        // Threads left running would keep consuming resources for solutions nobody will read
+        self.pool.stop();
+
+        for handle in self.handles.drain(..) {
+            let _r = handle.join();
+        }
+    }
+}
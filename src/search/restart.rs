@@ -0,0 +1,68 @@
+/// Schedule of failure limits after which search restarts from the root.
+///
+/// Restarts only happen while search is looking for its first solution, or when optimizing:
+/// the best objective value found so far is kept across restarts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RestartSchedule {
+    /// Same failure limit for every run.
+    ///
+    /// Search may never complete if the limit is too low to explore the whole tree in one run.
+    Constant(u64),
+
+    /// Failure limit multiplied by a constant factor after each run: `base * factor^n`.
+    Geometric {
+        /// Failure limit of the first run.
+        base: u64,
+
+        /// Growth factor of the failure limit, expected to be greater than one.
+        factor: f64,
+    },
+
+    /// Failure limits follow the Luby sequence `1, 1, 2, 1, 1, 2, 4, 1, ...`, times a scale.
+    Luby(u64),
+}
+
+impl RestartSchedule {
+    /// Failure limit for the run that follows the provided number of restarts.
+    pub(crate) fn get_failure_limit(self, n_restarts: u64) -> u64 {
+        let limit = match self {
+            Self::Constant(limit) => limit,
+            Self::Geometric { base, factor } => {
+                let exponent = i32::try_from(n_restarts).unwrap_or(i32::MAX);
+
+                // Float to integer casts saturate, which is the desired behavior for large limits
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_precision_loss,
+                    clippy::cast_sign_loss
+                )]
+                let limit = (base as f64 * factor.powi(exponent)) as u64;
+
+                limit
+            }
+            Self::Luby(scale) => scale.saturating_mul(luby(n_restarts + 1)),
+        };
+
+        // Each run must be allowed at least one failure, for search to make progress
+        limit.max(1)
+    }
+}
+
+/// Term of the Luby sequence, starting from index one.
+const fn luby(mut i: u64) -> u64 {
+    loop {
+        // Find the smallest complete sub-sequence that contains the term, of length `2^k - 1`
+        let mut k = 1;
+        while (1 << k) - 1 < i {
+            k += 1;
+        }
+
+        // Last term of a complete sub-sequence doubles the previous peak
+        if (1 << k) - 1 == i {
+            return 1 << (k - 1);
+        }
+
+        // Other terms repeat the start of the sequence
+        i -= (1 << (k - 1)) - 1;
+    }
+}
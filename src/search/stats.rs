@@ -0,0 +1,59 @@
+use core::time::Duration;
+
+use crate::search::SearchStatus;
+use crate::solution::Solution;
+
+/// Counters collected during search, to compare how models and strategies perform.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    /// Number of nodes explored, where each node is a space propagated after a branching decision.
+    pub nodes: u64,
+
+    /// Number of failed spaces.
+    pub failures: u64,
+
+    /// Largest number of nested branching decisions.
+    pub max_depth: usize,
+
+    /// Number of times each propagator of the model was run, in the order they were declared.
+    pub propagations: Vec<u64>,
+
+    /// Number of solutions found.
+    pub solutions: u64,
+
+    /// Number of times search was restarted from the root.
+    pub restarts: u64,
+
+    /// Wall-clock time spent since the start of search.
+    pub time: Duration,
+}
+
+impl Statistics {
+    /// Add counters of another search, run on a copy of the same model.
+    ///
+    /// Propagators declared after the model was copied are not tracked.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.nodes += other.nodes;
+        self.failures += other.failures;
+        self.max_depth = self.max_depth.max(other.max_depth);
+        self.solutions += other.solutions;
+        self.restarts += other.restarts;
+
+        for (n, other_n) in self.propagations.iter_mut().zip(&other.propagations) {
+            *n += other_n;
+        }
+    }
+}
+
+/// Outcome of a search, along with statistics on how it went.
+#[derive(Debug)]
+pub struct Report {
+    /// Best solution found, if any.
+    pub solution: Option<Solution>,
+
+    /// Guarantees search can offer on the solution.
+    pub status: SearchStatus,
+
+    /// Counters collected during search.
+    pub statistics: Statistics,
+}
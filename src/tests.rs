@@ -1,5 +1,11 @@
-use crate::views::ViewExt;
-use crate::{Model, Solution};
+use crate::props::Propagators;
+use crate::vars::Vars;
+use crate::views::{Context, View, ViewExt};
+use crate::{
+    Brancher, CancellationToken, Decision, Dfa, Lns, Model, Neighborhood, Propagate, Prune,
+    RandomNeighborhood, Relation, RestartSchedule, SearchLimits, SearchStatus, Solution, SpaceView,
+    ValueSelection, VarId, VarIdBinary, VariableSelection,
+};
 
 #[test]
 fn new_var() {
@@ -19,6 +25,15 @@ fn new_vars() {
     assert!(m.new_vars(5, 0, 1).is_some());
 }
 
+#[test]
+fn new_var_from_values() {
+    let mut m = Model::default();
+
+    assert!(m.new_var_from_values(&[]).is_none());
+    assert!(m.new_var_from_values(&[1, 1]).is_none());
+    assert!(m.new_var_from_values(&[3, 1]).is_some());
+}
+
 #[test]
 fn enumerate() {
     let mut m = Model::default();
@@ -35,6 +50,18 @@ fn enumerate() {
     assert_eq!(solutions, expected);
 }
 
+#[test]
+fn enumerate_values() {
+    let mut m = Model::default();
+
+    let x = m.new_var_from_values(&[7, 1, 3, 1]).unwrap();
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![1, 3, 7]);
+}
+
 #[test]
 fn minimize() {
     let mut m = Model::default();
@@ -53,6 +80,31 @@ fn maximize() {
     assert_eq!(m.maximize(x).unwrap()[x], 9);
 }
 
+#[test]
+fn assigned_with_pending_propagators() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 1).unwrap();
+    let y = m.new_var(0, 1).unwrap();
+
+    // First propagator assigns both variables, second one still has to reject the assignment
+    m.less_than(x, y);
+    m.less_than(y, x);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn bounds_skip_holes() {
+    let mut m = Model::default();
+
+    let x = m.new_var_from_values(&[1, 3, 7, 9]).unwrap();
+
+    m.greater_than(x, 4);
+
+    assert_eq!(m.minimize(x).unwrap()[x], 7);
+}
+
 #[test]
 fn opposite() {
     let mut m = Model::default();
@@ -207,6 +259,53 @@ fn times_neg_unfeasible() {
     assert!(m.solve().is_none());
 }
 
+#[test]
+fn constant_bounds_update() {
+    let mut vars = Vars::default();
+    let mut events = Vec::new();
+    let mut ctx = Context::new(&mut vars, &mut events);
+
+    assert_eq!(5.try_set_min(2, &mut ctx), Some(5));
+    assert_eq!(5.try_set_max(7, &mut ctx), Some(5));
+}
+
+#[test]
+fn opposite_bounds_update() {
+    let mut vars = Vars::default();
+    let x = vars.new_var_with_values(&[1, 4, 9]);
+
+    let mut events = Vec::new();
+    let mut ctx = Context::new(&mut vars, &mut events);
+
+    // Bounds are reported for the view, after skipping holes of the underlying domain
+    assert_eq!(x.opposite().try_set_max(-2, &mut ctx), Some(-4));
+    assert_eq!(x.opposite().try_set_min(-8, &mut ctx), Some(-4));
+}
+
+#[test]
+fn plus_bounds_update() {
+    let mut vars = Vars::default();
+    let x = vars.new_var_with_values(&[1, 4, 9]);
+
+    let mut events = Vec::new();
+    let mut ctx = Context::new(&mut vars, &mut events);
+
+    assert_eq!(x.plus(10).try_set_min(12, &mut ctx), Some(14));
+    assert_eq!(x.plus(10).try_set_max(18, &mut ctx), Some(14));
+}
+
+#[test]
+fn times_pos_bounds_update() {
+    let mut vars = Vars::default();
+    let x = vars.new_var_with_values(&[1, 4, 9]);
+
+    let mut events = Vec::new();
+    let mut ctx = Context::new(&mut vars, &mut events);
+
+    assert_eq!(x.times_pos(3).try_set_min(4, &mut ctx), Some(12));
+    assert_eq!(x.times_pos(3).try_set_max(26, &mut ctx), Some(12));
+}
+
 #[test]
 fn add() {
     let mut m = Model::default();
@@ -224,6 +323,179 @@ fn add() {
     assert_eq!(solution[p], 18);
 }
 
+#[test]
+fn mul() {
+    let mut m = Model::default();
+
+    let w = m.new_var(2, 10).unwrap();
+    let h = m.new_var(3, 5).unwrap();
+    let area = m.mul(w, h);
+
+    m.equals(area, 12);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| (s[w], s[h])).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![(3, 4), (4, 3)]);
+}
+
+#[test]
+fn mul_with_mixed_signs() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(-3, 2).unwrap();
+    let p = m.mul(x, y);
+
+    let solution = m.minimize(p).unwrap();
+
+    assert_eq!(solution[p], -27);
+    assert_eq!(solution[x], 9);
+    assert_eq!(solution[y], -3);
+}
+
+#[test]
+fn mul_with_zero_crossing() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(-3, 2).unwrap();
+    let p = m.mul(x, y);
+
+    m.equals(p, 0);
+    m.equals(y, 0);
+
+    assert_eq!(m.enumerate().count(), 17);
+}
+
+#[test]
+fn mul_unfeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(2, 9).unwrap();
+    let y = m.new_var(2, 9).unwrap();
+    let p = m.mul(x, y);
+
+    m.equals(p, 13);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn div() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(2, 3).unwrap();
+    let q = m.div(x, y);
+
+    m.equals(x, -7);
+    m.equals(y, 2);
+
+    assert_eq!(m.solve().unwrap()[q], -3);
+}
+
+#[test]
+fn div_by_zero_unfeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(-1, 1).unwrap();
+    let _q = m.div(x, y);
+
+    m.equals(y, 0);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn div_to_dividend() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let q = m.div(x, 4);
+
+    m.equals(q, -1);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![-7, -6, -5, -4]);
+}
+
+#[test]
+fn modulo() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let r = m.modulo(x, 3);
+
+    m.equals(x, -7);
+
+    assert_eq!(m.solve().unwrap()[r], -1);
+}
+
+#[test]
+fn modulo_to_dividend() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let r = m.modulo(x, 4);
+
+    m.equals(r, 3);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![3, 7]);
+}
+
+#[test]
+fn modulo_extreme_positive() {
+    let mut m = Model::default();
+
+    let x = m.new_var(i32::MIN, i32::MAX).unwrap();
+    let y = m.new_var(i32::MIN, i32::MAX).unwrap();
+    let r = m.modulo(x, y);
+
+    // Only `i32::MIN` is larger in magnitude than the largest remainder
+    m.equals(r, i32::MAX);
+
+    let solution = m.solve().unwrap();
+    assert_eq!(solution[x], i32::MAX);
+    assert_eq!(solution[y], i32::MIN);
+}
+
+#[test]
+fn modulo_extreme_negative() {
+    let mut m = Model::default();
+
+    let x = m.new_var(i32::MIN, i32::MAX).unwrap();
+    let y = m.new_var(i32::MIN, i32::MAX).unwrap();
+    let r = m.modulo(x, y);
+
+    m.equals(r, i32::MIN + 1);
+
+    let solution = m.solve().unwrap();
+    assert_eq!(solution[x], i32::MIN + 1);
+    assert_eq!(solution[y], i32::MIN);
+}
+
+#[test]
+fn abs() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let a = m.abs(x);
+
+    m.equals(a, 3);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![-3, 3]);
+}
+
 #[test]
 fn sum() {
     let mut m = Model::default();
@@ -239,6 +511,61 @@ fn sum() {
     assert_eq!(solution[s], 18);
 }
 
+#[test]
+fn min() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(4, 8).unwrap();
+    let s = m.min(&[x, y]);
+
+    m.greater_than(x, 5);
+
+    let solution = m.maximize(s).unwrap();
+
+    assert_eq!(solution[s], 8);
+    assert_eq!(solution[y], 8);
+}
+
+#[test]
+fn min_single_candidate() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(4, 8).unwrap();
+    let s = m.min(&[x, y]);
+
+    m.equals(s, 2);
+
+    assert_eq!(m.enumerate().map(|s| s[x]).collect::<Vec<_>>(), vec![2; 5]);
+}
+
+#[test]
+fn max() {
+    let mut m = Model::default();
+
+    let ends: Vec<_> = m.new_vars(3, 2, 9).unwrap().collect();
+    let makespan = m.max(&ends);
+
+    m.greater_than(ends[0], 4);
+    m.greater_than(ends[1], ends[2].plus(1));
+
+    let solution = m.minimize(makespan).unwrap();
+
+    assert_eq!(solution[makespan], 5);
+    assert_eq!(solution[ends[0]], 5);
+}
+
+#[test]
+fn max_empty_unfeasible() {
+    let mut m = Model::default();
+
+    let xs: [i32; 0] = [];
+    let _s = m.max(&xs);
+
+    assert!(m.solve().is_none());
+}
+
 #[test]
 fn equals() {
     let mut m = Model::default();
@@ -265,6 +592,48 @@ fn equals_with_constant() {
     assert_eq!(m.solve().unwrap()[x], 4);
 }
 
+#[test]
+fn not_equals() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-7, 9).unwrap();
+    let y = m.new_var(4, 9).unwrap();
+
+    m.equals(x, 5);
+    m.not_equals(x, y);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[y]).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![4, 6, 7, 8, 9]);
+}
+
+#[test]
+fn not_equals_with_constant() {
+    let mut m = Model::default();
+
+    let x = m.new_var(1, 3).unwrap();
+
+    m.not_equals(x, 2);
+    m.not_equals(x.times(2), 6);
+
+    assert_eq!(m.enumerate().map(|s| s[x]).collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn not_equals_unfeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(1, 2).unwrap();
+    let y = m.new_var(1, 2).unwrap();
+
+    m.equals(x, y);
+    m.equals(y, 2);
+    m.not_equals(x, 2);
+
+    assert!(m.solve().is_none());
+}
+
 #[test]
 fn less_than_or_equals() {
     let mut m = Model::default();
@@ -335,3 +704,1698 @@ fn greater_than() {
     assert_eq!(solution[x], 2);
     assert_eq!(solution[y], 1);
 }
+
+#[test]
+fn element() {
+    let mut m = Model::default();
+
+    let i = m.new_var(-7, 9).unwrap();
+    let s = m.element(i, &[40, 10, 30, 20]);
+
+    m.greater_than(s, 15);
+
+    let solution = m.minimize(s).unwrap();
+
+    assert_eq!(solution[i], 3);
+    assert_eq!(solution[s], 20);
+}
+
+#[test]
+fn element_index_from_value() {
+    let mut m = Model::default();
+
+    let i = m.new_var(-7, 9).unwrap();
+    let s = m.element(i, &[40, 10, 30, 20]);
+
+    m.equals(s, 30);
+
+    assert_eq!(m.enumerate().map(|s| s[i]).collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+fn element_unfeasible() {
+    let mut m = Model::default();
+
+    let i = m.new_var(-7, 9).unwrap();
+    let s = m.element(i, &[40, 10, 30, 20]);
+
+    m.equals(s, 25);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn element_var() {
+    let mut m = Model::default();
+
+    let i = m.new_var(-7, 9).unwrap();
+    let xs: Vec<_> = m.new_vars(3, 0, 5).unwrap().collect();
+    let s = m.element_var(i, &xs);
+
+    m.equals(xs[0], 1);
+    m.equals(xs[1], 4);
+    m.equals(xs[2], 2);
+    m.greater_than(s, 3);
+
+    let solution = m.solve().unwrap();
+
+    assert_eq!(solution[i], 1);
+    assert_eq!(solution[s], 4);
+}
+
+#[test]
+fn all_different() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(3, 1, 3).unwrap().collect();
+
+    m.all_different(&xs);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&xs)).collect();
+    solutions.sort();
+
+    let expected = vec![
+        vec![1, 2, 3],
+        vec![1, 3, 2],
+        vec![2, 1, 3],
+        vec![2, 3, 1],
+        vec![3, 1, 2],
+        vec![3, 2, 1],
+    ];
+
+    assert_eq!(solutions, expected);
+}
+
+#[test]
+fn all_different_hall_interval() {
+    let mut m = Model::default();
+
+    let x = m.new_var(1, 2).unwrap();
+    let y = m.new_var(1, 2).unwrap();
+    let z = m.new_var(1, 3).unwrap();
+
+    m.all_different(&[x, y, z]);
+
+    assert_eq!(m.minimize(z).unwrap()[z], 3);
+}
+
+#[test]
+fn all_different_unfeasible() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 1, 3).unwrap().collect();
+
+    m.all_different(&xs);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn all_different_domain() {
+    let mut m = Model::default();
+
+    let x = m.new_var(1, 2).unwrap();
+    let y = m.new_var(-2, -1).unwrap();
+    let z = m.new_var(1, 3).unwrap();
+
+    m.all_different_domain(&[x.times(1), y.times(-1), z.times(1)]);
+
+    assert_eq!(m.minimize(z).unwrap()[z], 3);
+}
+
+#[test]
+fn all_different_domain_wide() {
+    let mut m = Model::default();
+
+    // Values far apart must not make the matching graph grow with the spread of domains
+    let x = m
+        .new_var_from_values(&[-1_000_000_000, 1_000_000_000])
+        .unwrap();
+    let y = m
+        .new_var_from_values(&[-1_000_000_000, 1_000_000_000])
+        .unwrap();
+    let z = m
+        .new_var_from_values(&[-1_000_000_000, 0, 1_000_000_000])
+        .unwrap();
+
+    m.all_different_domain(&[x, y, z]);
+
+    assert_eq!(m.maximize(z).unwrap()[z], 0);
+}
+
+#[test]
+fn all_different_domain_removes_values() {
+    let mut m = Model::default();
+
+    let x = m.new_var(1, 2).unwrap();
+    let y = m.new_var(1, 2).unwrap();
+    let z = m.new_var(1, 4).unwrap();
+
+    m.all_different_domain(&[x, y, z]);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[z]).collect();
+    solutions.sort_unstable();
+    solutions.dedup();
+
+    assert_eq!(solutions, vec![3, 4]);
+}
+
+#[test]
+fn all_different_domain_unfeasible() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 1, 3).unwrap().collect();
+
+    m.all_different_domain(&xs);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn reify_less_than_or_equals() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 2).unwrap();
+    let y = m.new_var(0, 2).unwrap();
+    let b = m.reify_less_than_or_equals(x, y);
+
+    let solutions: Vec<_> = m.enumerate().collect();
+
+    assert_eq!(solutions.len(), 9);
+
+    for solution in solutions {
+        assert_eq!(solution.get_value_binary(b), solution[x] <= solution[y]);
+    }
+}
+
+#[test]
+fn reify_equals_entailed() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 2).unwrap();
+    let b = m.reify_equals(x, 1);
+
+    m.equals(x, 1);
+
+    assert!(m.solve().unwrap().get_value_binary(b));
+}
+
+#[test]
+fn reify_not_equals_disentailed() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 2).unwrap();
+    let b = m.reify_not_equals(x, 1);
+
+    m.equals(b, 1);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![0, 2]);
+}
+
+#[test]
+fn half_reify_less_than_or_equals() {
+    let mut m = Model::default();
+
+    // If the machine is used, its load must not exceed its capacity
+    let is_used = m.new_var_binary();
+    let load = m.new_var(0, 9).unwrap();
+
+    m.half_reify_less_than_or_equals(is_used, load, 5);
+
+    assert_eq!(m.maximize(load).unwrap()[load], 9);
+}
+
+#[test]
+fn half_reify_less_than_or_equals_enforced() {
+    let mut m = Model::default();
+
+    let is_used = m.new_var_binary();
+    let load = m.new_var(0, 9).unwrap();
+
+    m.half_reify_less_than_or_equals(is_used, load, 5);
+    m.equals(is_used, 1);
+
+    assert_eq!(m.maximize(load).unwrap()[load], 5);
+}
+
+#[test]
+fn half_reify_greater_than_disentailed() {
+    let mut m = Model::default();
+
+    let b = m.new_var_binary();
+    let x = m.new_var(0, 9).unwrap();
+
+    m.half_reify_greater_than(b, x, 5);
+    m.less_than(x, 3);
+
+    assert!(m.enumerate().all(|s| !s.get_value_binary(b)));
+}
+
+#[test]
+fn clause() {
+    let mut m = Model::default();
+
+    let x = m.new_var_binary();
+    let y = m.new_var_binary();
+    let z = m.new_var_binary();
+
+    // At least one of x and y is set, or z is not
+    m.clause(&[x, y], &[z]);
+    m.equals(x, 0);
+    m.equals(z, 1);
+
+    let solution = m.solve().unwrap();
+    assert!(solution.get_value_binary(y));
+}
+
+#[test]
+fn clause_empty_unfeasible() {
+    let mut m = Model::default();
+
+    let _x = m.new_var_binary();
+    m.clause(&[], &[]);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn clause_watched_triggers() {
+    let mut vars = Vars::default();
+    let mut props = Propagators::default();
+
+    let xs: Vec<_> = (0..4)
+        .map(|_| {
+            props.on_new_var();
+            VarIdBinary(vars.new_var_with_bounds(0, 1))
+        })
+        .collect();
+
+    let p = props.clause(xs.iter().map(|&x| (x, true)).collect());
+
+    let get_degrees =
+        |props: &Propagators| -> Vec<_> { xs.iter().map(|x| props.get_degree(x.0, 1)).collect() };
+
+    // Only the first and last literals are watched at first
+    assert_eq!(get_degrees(&props), [1, 0, 0, 1]);
+
+    let mut events = Vec::new();
+    let mut ctx = Context::new(&mut vars, &mut events);
+
+    // Falsified watch moves to the next literal, along with the subscription
+    assert_eq!(xs[0].try_set_max(0, &mut ctx), Some(0));
+    assert_eq!(props.get_state_mut(p).prune(&mut ctx), Some(()));
+
+    for (from, to) in ctx.into_trigger_moves() {
+        props.move_trigger_var(p, from, to);
+    }
+
+    assert_eq!(get_degrees(&props), [0, 1, 0, 1]);
+}
+
+#[test]
+fn implies() {
+    let mut m = Model::default();
+
+    let x = m.new_var_binary();
+    let y = m.new_var_binary();
+
+    m.implies(x, y);
+
+    let solutions: Vec<_> = m.enumerate().collect();
+
+    assert_eq!(solutions.len(), 3);
+    assert!(solutions
+        .iter()
+        .all(|s| !s.get_value_binary(x) || s.get_value_binary(y)));
+}
+
+#[test]
+fn not() {
+    let mut m = Model::default();
+
+    let x = m.new_var_binary();
+    let not_x = m.not(x);
+
+    assert!(m
+        .enumerate()
+        .all(|s| s.get_value_binary(x) != s.get_value_binary(not_x)));
+}
+
+#[test]
+fn and() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars_binary(3).collect();
+    let b = m.and(&xs);
+
+    let solutions: Vec<_> = m.enumerate().collect();
+
+    assert_eq!(solutions.len(), 8);
+    assert!(solutions
+        .iter()
+        .all(|s| s.get_value_binary(b) == s.get_values_binary(&xs).iter().all(|&v| v)));
+}
+
+#[test]
+fn or() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars_binary(3).collect();
+    let b = m.or(&xs);
+    m.equals(b, 0);
+
+    let solution = m.solve().unwrap();
+    assert_eq!(solution.get_values_binary(&xs), vec![false; 3]);
+}
+
+#[test]
+fn or_watched_operands() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars_binary(10).collect();
+    let b = m.or(&xs);
+
+    // Operands in the middle are branched on first, but only the first and last ones are watched
+    m.branch_on(
+        &xs[1..9],
+        VariableSelection::InputOrder,
+        ValueSelection::AssignMin,
+    );
+
+    let report = m.solve_and_report();
+
+    assert!(!report.solution.unwrap().get_value_binary(b));
+    assert_eq!(report.statistics.propagations.len(), 1);
+    assert!(report.statistics.propagations[0] < 8);
+}
+
+#[test]
+fn xor() {
+    let mut m = Model::default();
+
+    let x = m.new_var_binary();
+    let y = m.new_var_binary();
+    let b = m.xor(x, y);
+
+    let solutions: Vec<_> = m.enumerate().collect();
+
+    assert_eq!(solutions.len(), 4);
+    assert!(solutions
+        .iter()
+        .all(|s| s.get_value_binary(b) == (s.get_value_binary(x) != s.get_value_binary(y))));
+}
+
+#[test]
+fn linear_less_than_or_equals() {
+    let mut m = Model::default();
+
+    // Knapsack capacity, without an intermediate variable for the total weight
+    let xs: Vec<_> = m.new_vars_binary(4).collect();
+    let terms: Vec<_> = xs.iter().zip([5, 4, 3, 2]).map(|(&x, w)| (w, x)).collect();
+    m.linear(&terms, Relation::Le, 7);
+
+    let value = m.sum_iter(xs.iter().zip([6, 4, 3, 2]).map(|(x, v)| x.times(v)));
+
+    let solution = m.maximize(value).unwrap();
+    assert_eq!(solution[value], 8);
+}
+
+#[test]
+fn linear_equals() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 10).unwrap();
+    let y = m.new_var(0, 10).unwrap();
+
+    // 3x - 2y == 1
+    m.linear(&[(3, x), (-2, y)], Relation::Eq, 1);
+
+    let solutions: Vec<_> = m.enumerate().collect();
+
+    assert_eq!(solutions.len(), 4);
+    assert!(solutions.iter().all(|s| 3 * s[x] - 2 * s[y] == 1));
+}
+
+#[test]
+fn linear_greater_than_or_equals() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 10).unwrap();
+    let y = m.new_var(0, 10).unwrap();
+
+    m.linear(&[(2, x), (1, y)], Relation::Ge, 15);
+
+    let solution = m.minimize(x).unwrap();
+    assert_eq!(solution[x], 3);
+}
+
+#[test]
+fn linear_not_equals() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 2).unwrap();
+    let y = m.new_var(0, 2).unwrap();
+
+    m.linear(&[(1, x), (1, y)], Relation::Ne, 2);
+
+    assert_eq!(m.enumerate().count(), 6);
+}
+
+#[test]
+fn linear_unfeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 3).unwrap();
+    let y = m.new_var(0, 3).unwrap();
+
+    m.linear(&[(2, x), (2, y)], Relation::Eq, 7);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn variable_selection_finds_all_solutions() {
+    let strategies = [
+        VariableSelection::InputOrder,
+        VariableSelection::SmallestDomain,
+        VariableSelection::LargestDomain,
+        VariableSelection::SmallestMin,
+        VariableSelection::DomOverDeg,
+        VariableSelection::DomOverWdeg,
+    ];
+
+    for selection in strategies {
+        let mut m = Model::default();
+
+        let x = m.new_var(0, 9).unwrap();
+        let y = m.new_var(2, 4).unwrap();
+        let z = m.new_var(-3, 3).unwrap();
+
+        m.all_different(&[x, y, z]);
+        m.linear(&[(1, x), (2, y), (-1, z)], Relation::Eq, 8);
+        m.set_variable_selection(selection);
+
+        let mut solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&[x, y, z])).collect();
+        solutions.sort_unstable();
+
+        let mut expected = Vec::new();
+        for vx in 0..=9 {
+            for vy in 2..=4 {
+                for vz in -3..=3 {
+                    if vx != vy && vx != vz && vy != vz && vx + 2 * vy - vz == 8 {
+                        expected.push(vec![vx, vy, vz]);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(solutions, expected, "{selection:?}");
+    }
+}
+
+#[test]
+fn variable_selection_queens() {
+    let mut m = Model::default();
+
+    // One queen per column, on the row given by the variable
+    let rows: Vec<_> = m.new_vars(8, 0, 7).unwrap().collect();
+    let diagonals_up: Vec<_> = (0..).zip(&rows).map(|(i, r)| r.plus(i)).collect();
+    let diagonals_down: Vec<_> = (0..).zip(&rows).map(|(i, r)| r.plus(-i)).collect();
+
+    m.all_different(&rows);
+    m.all_different(&diagonals_up);
+    m.all_different(&diagonals_down);
+    m.set_variable_selection(VariableSelection::DomOverWdeg);
+
+    assert_eq!(m.enumerate().count(), 92);
+}
+
+#[test]
+fn value_selection_assign_max() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    m.set_value_selection(ValueSelection::AssignMax);
+
+    assert_eq!(m.solve().unwrap()[x], 9);
+}
+
+#[test]
+fn value_selection_split_upper() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    m.set_value_selection(ValueSelection::SplitUpper);
+
+    let values: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    assert_eq!(values, (0..=9).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn value_selection_assign_median() {
+    let mut m = Model::default();
+
+    let x = m.new_var_from_values(&[1, 2, 7, 8, 9]).unwrap();
+    m.set_value_selection(ValueSelection::AssignMedian);
+
+    assert_eq!(m.solve().unwrap()[x], 7);
+}
+
+#[test]
+fn value_selection_assign_random() {
+    let solve_with_seed = |seed| {
+        let mut m = Model::default();
+
+        let xs: Vec<_> = m.new_vars(4, 0, 99).unwrap().collect();
+        m.set_value_selection(ValueSelection::AssignRandom { seed });
+
+        m.solve().unwrap().get_values(&xs)
+    };
+
+    // Same seed leads to the same search
+    assert_eq!(solve_with_seed(7), solve_with_seed(7));
+    assert_ne!(solve_with_seed(7), vec![0; 4]);
+}
+
+#[test]
+fn value_selection_custom() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+
+    // Values that are not in the domain fall back to its minimum
+    m.set_value_selection(ValueSelection::custom(|_, domain| domain.mid() + 10));
+    m.branch_on(
+        &[x],
+        VariableSelection::InputOrder,
+        ValueSelection::custom(|_, _| 5),
+    );
+
+    let solution = m.solve().unwrap();
+    assert_eq!(solution[x], 5);
+    assert_eq!(solution[y], 0);
+}
+
+#[test]
+fn branch_on_groups_in_order() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    let b = m.new_var_binary();
+
+    m.less_than(x, y);
+    m.branch_on(
+        &[b],
+        VariableSelection::InputOrder,
+        ValueSelection::AssignMax,
+    );
+    m.branch_on(
+        &[y],
+        VariableSelection::InputOrder,
+        ValueSelection::AssignMin,
+    );
+
+    let solution = m.solve().unwrap();
+    assert!(solution.get_value_binary(b));
+    assert_eq!(solution[y], 1);
+    assert_eq!(solution[x], 0);
+}
+
+/// Branch on binary variables, trying to set them before unsetting them.
+#[derive(Debug)]
+struct SetBinariesFirst(Vec<VarIdBinary>);
+
+impl Brancher for SetBinariesFirst {
+    fn branch(&self, space: &SpaceView<'_>) -> Option<Vec<Decision>> {
+        let &x = self.0.iter().find(|&&x| !space.is_assigned(x))?;
+
+        Some(vec![Decision::equals(x, 1), Decision::equals(x, 0)])
+    }
+}
+
+/// Schedule tasks at their earliest start time, or postpone them on backtrack.
+#[derive(Debug)]
+struct SetTimes(Vec<VarId>);
+
+impl Brancher for SetTimes {
+    fn branch(&self, space: &SpaceView<'_>) -> Option<Vec<Decision>> {
+        let &start = self
+            .0
+            .iter()
+            .filter(|&&start| !space.is_assigned(start))
+            .min_by_key(|&&start| space.min(start))?;
+
+        let earliest = space.min(start);
+
+        Some(vec![
+            Decision::equals(start, earliest),
+            Decision::greater_than(start, earliest),
+        ])
+    }
+}
+
+#[test]
+fn brancher_custom() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars_binary(3).collect();
+    m.branch_with(SetBinariesFirst(xs.clone()));
+
+    let solutions: Vec<_> = m.enumerate().map(|s| s.get_values_binary(&xs)).collect();
+
+    assert_eq!(solutions.len(), 8);
+    assert_eq!(solutions[0], vec![true; 3]);
+    assert_eq!(solutions[7], vec![false; 3]);
+}
+
+#[test]
+fn brancher_chained() {
+    let mut m = Model::default();
+
+    let a = m.new_var(0, 10).unwrap();
+    let b = m.new_var(0, 10).unwrap();
+    let is_late = m.new_var_binary();
+
+    // Task `a` comes before task `b`, which is late if it starts after time 5
+    m.less_than_or_equals(a.plus(4), b);
+    m.half_reify_greater_than(is_late, b, 5);
+
+    // Decide lateness first, then schedule tasks as early as possible
+    m.branch_with(SetBinariesFirst(vec![is_late]));
+    m.branch_with(SetTimes(vec![a, b]));
+
+    let solution = m.solve().unwrap();
+    assert!(solution.get_value_binary(is_late));
+    assert_eq!(solution[a], 0);
+    assert_eq!(solution[b], 6);
+}
+
+#[test]
+fn brancher_without_alternatives() {
+    #[derive(Debug)]
+    struct Fail;
+
+    impl Brancher for Fail {
+        fn branch(&self, _: &SpaceView<'_>) -> Option<Vec<Decision>> {
+            Some(Vec::new())
+        }
+    }
+
+    let mut m = Model::default();
+
+    let _x = m.new_var(0, 1).unwrap();
+    m.branch_with(Fail);
+
+    assert!(m.solve().is_none());
+}
+
+/// User-defined propagator, to restrict a view to even values.
+#[derive(Clone, Debug)]
+struct Even<V>(V);
+
+impl<V: View> Prune for Even<V> {
+    fn prune(&mut self, ctx: &mut Context) -> Option<()> {
+        // Bounds are moved inwards until they are even, which fails odd assignments
+        let min = self.0.min(ctx);
+        let _min = self.0.try_set_min(min + min.rem_euclid(2), ctx)?;
+
+        let max = self.0.max(ctx);
+        let _max = self.0.try_set_max(max - max.rem_euclid(2), ctx)?;
+
+        Some(())
+    }
+}
+
+impl<V: View> Propagate for Even<V> {
+    fn list_trigger_vars(&self) -> impl Iterator<Item = VarId> {
+        self.0.get_underlying_var().into_iter()
+    }
+}
+
+#[test]
+fn post_propagator() {
+    let mut m = Model::default();
+
+    let x = m.new_var(-3, 7).unwrap();
+    m.post_propagator(Even(x));
+
+    let values: Vec<_> = m.enumerate().map(|s| s[x]).collect();
+    assert_eq!(values, vec![-2, 0, 2, 4, 6]);
+}
+
+#[test]
+fn post_propagator_unfeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    m.post_propagator(Even(x.times(3).plus(1)));
+    m.equals(x, 2);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn search_status_optimal() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    m.less_than(x, y);
+
+    let mut solutions = m.maximize_and_iterate(x);
+
+    // Solutions found before exhausting the search tree are not proven optimal
+    assert!(solutions.next().is_some());
+    assert_eq!(solutions.status(), SearchStatus::Feasible);
+
+    assert_eq!(solutions.by_ref().last().unwrap()[x], 8);
+    assert_eq!(solutions.status(), SearchStatus::Optimal);
+}
+
+#[test]
+fn search_status_infeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    m.greater_than(x, 9);
+
+    let mut solutions = m.enumerate();
+
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.status(), SearchStatus::Infeasible);
+}
+
+#[test]
+fn search_limits_solutions() {
+    let mut m = Model::default();
+
+    let _xs: Vec<_> = m.new_vars_binary(4).collect();
+    m.set_limits(SearchLimits {
+        solutions: Some(3),
+        ..SearchLimits::default()
+    });
+
+    let mut solutions = m.enumerate();
+
+    assert_eq!(solutions.by_ref().count(), 3);
+    assert_eq!(solutions.status(), SearchStatus::Feasible);
+}
+
+/// Pigeonhole problem, which pairwise constraints only prove infeasible after a long search.
+fn pigeonhole() -> Model {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(12, 0, 10).unwrap().collect();
+    for (i, &x) in xs.iter().enumerate() {
+        for &y in &xs[i + 1..] {
+            m.not_equals(x, y);
+        }
+    }
+
+    m
+}
+
+#[test]
+fn search_limits_nodes() {
+    let mut m = pigeonhole();
+
+    m.set_limits(SearchLimits {
+        nodes: Some(100),
+        ..SearchLimits::default()
+    });
+
+    let mut solutions = m.enumerate();
+
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.status(), SearchStatus::Unknown);
+}
+
+#[test]
+fn search_limits_failures() {
+    let mut m = pigeonhole();
+
+    m.set_limits(SearchLimits {
+        failures: Some(5),
+        ..SearchLimits::default()
+    });
+
+    let mut solutions = m.enumerate();
+
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.status(), SearchStatus::Unknown);
+}
+
+#[test]
+fn search_limits_time() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    m.less_than(x, y);
+
+    m.set_limits(SearchLimits {
+        time: Some(core::time::Duration::ZERO),
+        ..SearchLimits::default()
+    });
+
+    let mut solutions = m.minimize_and_iterate(y);
+
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.status(), SearchStatus::Unknown);
+}
+
+#[test]
+fn cancellation_token_before_search() {
+    let m = pigeonhole();
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let mut solutions = m.enumerate_with_token(token);
+
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.status(), SearchStatus::Unknown);
+}
+
+#[test]
+fn cancellation_token_from_other_thread() {
+    let token = CancellationToken::new();
+
+    // Exhaustive search would take a long time, unless it gets cancelled
+    let worker = std::thread::spawn({
+        let token = token.clone();
+
+        move || {
+            let m = pigeonhole();
+
+            let mut solutions = m.enumerate_with_token(token);
+            let solution = solutions.next();
+
+            (solution, solutions.status())
+        }
+    });
+
+    std::thread::sleep(core::time::Duration::from_millis(10));
+    token.cancel();
+
+    let (solution, status) = worker.join().unwrap();
+
+    assert!(token.is_cancelled());
+    assert!(solution.is_none());
+    assert_eq!(status, SearchStatus::Unknown);
+}
+
+#[test]
+fn cancellation_token_not_kept_by_model() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    m.less_than(x, y);
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    // Cancelled token only stops the search it was passed to
+    let mut solutions = m.clone().minimize_and_iterate_with_token(y, token);
+    assert!(solutions.next().is_none());
+    assert_eq!(solutions.status(), SearchStatus::Unknown);
+
+    assert_eq!(m.clone().minimize(y).unwrap()[y], 1);
+    assert_eq!(m.solve_with_token(CancellationToken::new()).unwrap()[y], 1);
+}
+
+#[test]
+fn statistics_on_iterator() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    m.less_than(x, y);
+
+    let mut solutions = m.maximize_and_iterate(x);
+    let n_solutions = solutions.by_ref().count();
+
+    let statistics = solutions.statistics();
+    assert_eq!(statistics.solutions, n_solutions as u64);
+    assert!(statistics.nodes >= statistics.solutions);
+    assert!(statistics.max_depth > 0);
+    assert_eq!(statistics.propagations.len(), 1);
+    assert!(statistics.propagations[0] > 0);
+    assert_eq!(statistics.restarts, 0);
+
+    // Wall-clock time is frozen once search is over
+    assert_eq!(solutions.statistics().time, statistics.time);
+}
+
+#[test]
+fn report_from_solve() {
+    let mut m = pigeonhole();
+
+    m.set_limits(SearchLimits {
+        nodes: Some(100),
+        ..SearchLimits::default()
+    });
+
+    let report = m.solve_and_report();
+
+    assert!(report.solution.is_none());
+    assert_eq!(report.status, SearchStatus::Unknown);
+    assert_eq!(report.statistics.nodes, 100);
+    assert!(report.statistics.failures > 0);
+    assert_eq!(report.statistics.solutions, 0);
+}
+
+#[test]
+fn report_from_maximize() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    m.less_than(x, y);
+
+    let report = m.maximize_and_report(x);
+
+    assert_eq!(report.solution.unwrap()[x], 8);
+    assert_eq!(report.status, SearchStatus::Optimal);
+    assert!(report.statistics.solutions > 0);
+}
+
+#[test]
+fn restart_schedule_luby() {
+    let limits: Vec<_> = (0..15)
+        .map(|n| RestartSchedule::Luby(10).get_failure_limit(n))
+        .collect();
+
+    let sequence = [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8];
+    assert_eq!(limits, sequence.map(|x| 10 * x));
+}
+
+#[test]
+fn restart_schedule_geometric() {
+    let schedule = RestartSchedule::Geometric {
+        base: 10,
+        factor: 1.5,
+    };
+
+    let limits: Vec<_> = (0..4).map(|n| schedule.get_failure_limit(n)).collect();
+    assert_eq!(limits, vec![10, 15, 22, 33]);
+}
+
+#[test]
+fn restarts_with_failure_limit() {
+    let mut m = pigeonhole();
+
+    // Pigeonhole constraints make the problem infeasible, which takes a long search to prove
+    let xs: Vec<_> = m.new_vars(3, 0, 9).unwrap().collect();
+    let objective = m.sum(&xs);
+    m.all_different(&xs);
+
+    m.set_variable_selection(VariableSelection::DomOverWdeg);
+    m.set_value_selection(ValueSelection::AssignRandom { seed: 1 });
+    m.set_restarts(RestartSchedule::Luby(4));
+    m.set_limits(SearchLimits {
+        failures: Some(2000),
+        ..SearchLimits::default()
+    });
+
+    let report = m.maximize_and_report(objective);
+
+    assert!(report.solution.is_none());
+    assert!(report.statistics.restarts > 0);
+    assert_eq!(report.status, SearchStatus::Unknown);
+}
+
+#[test]
+fn restarts_keep_bound() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 0, 9).unwrap().collect();
+    m.all_different(&xs);
+    m.linear(
+        &[(3, xs[0]), (2, xs[1]), (1, xs[2]), (-1, xs[3])],
+        Relation::Le,
+        14,
+    );
+
+    let objective = m.sum(&xs);
+
+    // Runs are too short to prove optimality, unless the best bound is kept across restarts
+    m.set_value_selection(ValueSelection::AssignRandom { seed: 1 });
+    m.set_restarts(RestartSchedule::Constant(5));
+
+    let report = m.maximize_and_report(objective);
+
+    assert_eq!(report.solution.unwrap()[objective], 24);
+    assert_eq!(report.status, SearchStatus::Optimal);
+    assert!(report.statistics.restarts > 0);
+}
+
+#[test]
+fn lns_improves_incumbent() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(6, 0, 9).unwrap().collect();
+    m.all_different(&xs);
+
+    let objective = m.sum(&xs);
+
+    // Freeing two variables at a time is enough to reach the optimum, but not to prove it
+    let mut lns = Lns::new(RandomNeighborhood::new(&xs, 2));
+    lns.iterations = Some(200);
+    lns.seed = 7;
+
+    let report = m.maximize_with_lns(objective, &lns);
+
+    assert_eq!(report.solution.unwrap()[objective], 39);
+    assert_eq!(report.status, SearchStatus::Feasible);
+    assert!(report.statistics.solutions > 1);
+}
+
+#[test]
+fn lns_proves_optimality_without_fixed_variables() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 0, 9).unwrap().collect();
+    m.all_different(&xs);
+
+    let objective = m.sum(&xs);
+
+    let report = m.maximize_with_lns(objective, &Lns::new(RandomNeighborhood::new(&xs, 4)));
+
+    assert_eq!(report.solution.unwrap()[objective], 30);
+    assert_eq!(report.status, SearchStatus::Optimal);
+}
+
+#[test]
+fn lns_infeasible() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 9).unwrap();
+    let y = m.new_var(0, 9).unwrap();
+    m.less_than(x, y);
+    m.less_than(y, x);
+
+    let mut lns = Lns::new(RandomNeighborhood::new(&[x, y], 1));
+    lns.iterations = Some(10);
+
+    let report = m.minimize_with_lns(x, &lns);
+
+    assert!(report.solution.is_none());
+    assert_eq!(report.status, SearchStatus::Infeasible);
+}
+
+#[test]
+fn lns_custom_neighborhood() {
+    #[derive(Debug)]
+    struct KeepFirst(VarId);
+
+    impl Neighborhood for KeepFirst {
+        fn select(&self, _: &Solution, _: u64) -> Vec<VarId> {
+            vec![self.0]
+        }
+    }
+
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(3, 0, 9).unwrap().collect();
+    m.all_different(&xs);
+
+    let objective = m.sum(&xs);
+
+    // First variable keeps the value it gets in the initial solution
+    let mut lns = Lns::new(KeepFirst(xs[0]));
+    lns.iterations = Some(5);
+
+    let report = m.maximize_with_lns(objective, &lns);
+    let solution = report.solution.unwrap();
+
+    assert_eq!(solution[xs[0]], 0);
+    assert_eq!(solution[objective], 17);
+}
+
+#[test]
+fn lns_same_seed_same_solutions() {
+    let run = |seed| {
+        let mut m = Model::default();
+
+        let xs: Vec<_> = m.new_vars(8, 0, 20).unwrap().collect();
+        m.all_different(&xs);
+        m.linear(&[(2, xs[0]), (3, xs[1]), (-1, xs[7])], Relation::Le, 25);
+
+        let objective = m.sum(&xs);
+
+        let mut lns = Lns::new(RandomNeighborhood::new(&xs, 3));
+        lns.iterations = Some(20);
+        lns.failures = 10;
+        lns.seed = seed;
+
+        m.maximize_with_lns(objective, &lns).solution.unwrap()
+    };
+
+    assert_eq!(run(3), run(3));
+}
+
+#[test]
+fn parallel_enumerate() {
+    let build = |threads| {
+        let mut m = Model::default();
+
+        let xs: Vec<_> = m.new_vars(4, 0, 5).unwrap().collect();
+        m.all_different(&xs);
+        m.linear(&[(2, xs[0]), (1, xs[1]), (-1, xs[3])], Relation::Le, 6);
+        m.set_threads(threads);
+
+        let mut solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&xs)).collect();
+        solutions.sort_unstable();
+        solutions
+    };
+
+    let solutions = build(1);
+
+    assert!(!solutions.is_empty());
+    assert_eq!(build(4), solutions);
+}
+
+#[test]
+fn parallel_minimize() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(5, 0, 9).unwrap().collect();
+    m.all_different(&xs);
+    m.linear(&[(3, xs[0]), (2, xs[1]), (-1, xs[4])], Relation::Ge, 20);
+
+    let objective = m.sum(&xs);
+
+    m.set_threads(4);
+
+    let mut solutions = m.minimize_and_iterate(objective);
+    let values: Vec<_> = solutions.by_ref().map(|s| s[objective]).collect();
+
+    // Solutions found by all threads are yielded in strictly improving order
+    assert!(values.windows(2).all(|w| w[0] > w[1]));
+    assert_eq!(values.last(), Some(&11));
+    assert_eq!(solutions.status(), SearchStatus::Optimal);
+}
+
+#[test]
+fn parallel_infeasible() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(6, 0, 4).unwrap().collect();
+    for (i, &x) in xs.iter().enumerate() {
+        for &y in &xs[i + 1..] {
+            m.not_equals(x, y);
+        }
+    }
+
+    m.set_threads(3);
+
+    let report = m.solve_and_report();
+
+    assert!(report.solution.is_none());
+    assert_eq!(report.status, SearchStatus::Infeasible);
+}
+
+#[test]
+fn parallel_limits_are_global() {
+    let mut m = pigeonhole();
+
+    m.set_threads(4);
+    m.set_limits(SearchLimits {
+        nodes: Some(100),
+        ..SearchLimits::default()
+    });
+
+    let report = m.solve_and_report();
+
+    // Threads may each explore one more node after the limit is reached
+    assert!(report.solution.is_none());
+    assert_eq!(report.status, SearchStatus::Unknown);
+    assert!(report.statistics.nodes < 100 + 4);
+}
+
+#[test]
+fn portfolio_minimize() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(5, 0, 9).unwrap().collect();
+    m.all_different(&xs);
+    m.linear(&[(3, xs[0]), (2, xs[1]), (-1, xs[4])], Relation::Ge, 20);
+
+    let objective = m.sum(&xs);
+
+    // Member that cannot complete its search does not prevent others from proving optimality
+    let mut limited = m.clone();
+    limited.set_limits(SearchLimits {
+        failures: Some(1),
+        ..SearchLimits::default()
+    });
+
+    let mut randomized = m.clone();
+    randomized.set_variable_selection(VariableSelection::DomOverWdeg);
+    randomized.set_value_selection(ValueSelection::AssignRandom { seed: 3 });
+    randomized.set_restarts(RestartSchedule::Luby(4));
+
+    let report = Model::minimize_portfolio(vec![limited, randomized, m], objective);
+
+    assert_eq!(report.solution.unwrap()[objective], 11);
+    assert_eq!(report.status, SearchStatus::Optimal);
+}
+
+#[test]
+fn portfolio_solve() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(3, 0, 2).unwrap().collect();
+    m.all_different(&xs);
+
+    let mut reversed = m.clone();
+    reversed.set_value_selection(ValueSelection::AssignMax);
+
+    let report = Model::solve_portfolio(vec![m, reversed]);
+    let mut values = report.solution.unwrap().get_values(&xs);
+    values.sort_unstable();
+
+    assert_eq!(values, vec![0, 1, 2]);
+    assert_eq!(report.status, SearchStatus::Feasible);
+}
+
+#[test]
+fn table() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 3).unwrap();
+    let y = m.new_var(0, 3).unwrap();
+    let z = m.new_var(0, 3).unwrap();
+
+    let tuples = [
+        vec![0, 1, 2],
+        vec![1, 1, 3],
+        vec![2, 0, 0],
+        vec![3, 3, 3],
+        vec![1, 2],
+    ];
+    m.table(&[x, y, z], &tuples);
+    m.not_equals(x, z);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&[x, y, z])).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![vec![0, 1, 2], vec![1, 1, 3], vec![2, 0, 0]]);
+}
+
+#[test]
+fn table_empty() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 3).unwrap();
+    let y = m.new_var(0, 3).unwrap();
+    m.table(&[x, y], &[vec![5, 0], vec![1, 7]]);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn table_arc_consistency() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(3, 0, 9).unwrap().collect();
+
+    // Many tuples span several words of the underlying bitsets
+    let tuples: Vec<_> = (0..10)
+        .flat_map(|a| (0..10).map(move |b| vec![a, b, (a * b) % 7]))
+        .filter(|t| t[0] + t[1] >= 5)
+        .collect();
+
+    m.table(&xs, &tuples);
+
+    let mut solutions = m.enumerate();
+    let count = solutions.by_ref().count();
+
+    // Every value left after propagation belongs to a solution, search never backtracks
+    assert_eq!(count, tuples.len());
+    assert_eq!(solutions.statistics().failures, 0);
+}
+
+#[test]
+fn negative_table() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 1).unwrap();
+    let y = m.new_var(0, 2).unwrap();
+
+    // Forbidding every tuple with `x == 0` removes the value from its domain
+    m.negative_table(&[x, y], &[vec![0, 0], vec![0, 1], vec![0, 2], vec![1, 1]]);
+
+    let mut solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&[x, y])).collect();
+    solutions.sort_unstable();
+
+    assert_eq!(solutions, vec![vec![1, 0], vec![1, 2]]);
+}
+
+/// Shifts are encoded as `0` for a day off, `1` for a day shift and `2` for a night shift.
+///
+/// At most three night shifts in a row, always followed by at least two days off.
+fn shifts() -> Dfa {
+    let transitions = [
+        (0, 0, 0),
+        (0, 1, 0),
+        (0, 2, 1),
+        (1, 2, 2),
+        (2, 2, 3),
+        (1, 0, 4),
+        (2, 0, 4),
+        (3, 0, 4),
+        (4, 0, 0),
+    ];
+
+    Dfa::new(5, 0, &[0, 1, 2, 3, 4], &transitions).unwrap()
+}
+
+#[test]
+fn dfa_new() {
+    assert!(Dfa::new(2, 2, &[], &[]).is_none());
+    assert!(Dfa::new(2, 0, &[2], &[]).is_none());
+    assert!(Dfa::new(2, 0, &[1], &[(0, 5, 2)]).is_none());
+    assert!(Dfa::new(2, 0, &[1], &[(0, 5, 1), (0, 5, 0)]).is_none());
+    assert!(Dfa::new(2, 0, &[1], &[(0, 5, 1), (0, 5, 1)]).is_some());
+}
+
+#[test]
+fn dfa_accepts() {
+    let dfa = shifts();
+
+    assert!(dfa.accepts(&[1, 2, 2, 2, 0, 0, 1]));
+    assert!(!dfa.accepts(&[2, 2, 2, 2, 0, 0, 1]));
+    assert!(!dfa.accepts(&[2, 0, 1]));
+    assert!(!dfa.accepts(&[2, 1]));
+}
+
+#[test]
+fn regular() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(7, 0, 2).unwrap().collect();
+    m.regular(&xs, shifts());
+
+    // Three nights in a row at the start of the week force two days off right after
+    m.equals(xs[0], 2);
+    m.equals(xs[2], 2);
+
+    let solution = m.clone().solve().unwrap();
+    assert_eq!(solution.get_values(&xs[..5]), vec![2, 2, 2, 0, 0]);
+
+    let mut solutions = m.enumerate();
+    let count = solutions.by_ref().count();
+
+    // Remaining two days: any pair but a day shift after a night shift
+    assert_eq!(count, 8);
+    assert_eq!(solutions.statistics().failures, 0);
+}
+
+#[test]
+fn cumulative() {
+    let mut m = Model::default();
+
+    let starts: Vec<_> = m.new_vars(3, 0, 10).unwrap().collect();
+    let end = m.new_var(0, 20).unwrap();
+
+    let durations = [3, 2, 4];
+    let demands = [2, 1, 2];
+
+    for (&s, &d) in starts.iter().zip(&durations) {
+        m.less_than_or_equals(s.plus(d), end);
+    }
+
+    m.cumulative(&starts, &durations, &demands, 3);
+
+    // Tasks with a demand of two cannot overlap, the short one fits alongside either
+    let solution = m.minimize(end).unwrap();
+    assert_eq!(solution[end], 7);
+}
+
+#[test]
+fn cumulative_offsets() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 5).unwrap();
+    let y = m.new_var(0, 5).unwrap();
+
+    // Second task starts two units after its variable
+    m.cumulative(&[x.plus(0), y.plus(2)], &[3, 3], &[1, 1], 1);
+    m.equals(x, 1);
+
+    let solutions: Vec<_> = m.enumerate().map(|s| s[y]).collect();
+    assert_eq!(solutions, vec![2, 3, 4, 5]);
+}
+
+#[test]
+fn cumulative_demand_exceeds_capacity() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 5).unwrap();
+    m.cumulative(&[x], &[1], &[4], 3);
+
+    assert!(m.solve().is_none());
+}
+
+#[test]
+fn cumulative_edge_finding() {
+    let build = || {
+        let mut m = Model::default();
+
+        let a = m.new_var(0, 2).unwrap();
+        let b = m.new_var(0, 2).unwrap();
+        let c = m.new_var(0, 3).unwrap();
+
+        (m, [a, b, c])
+    };
+
+    // Tasks `a` and `b` fill window `[0, 4]`, so `c` has to start after it
+    let (mut m, starts) = build();
+    m.cumulative_edge_finding(&starts, &[2, 2, 2], &[1, 1, 1], 1);
+
+    let report = m.solve_and_report();
+    assert_eq!(report.status, SearchStatus::Infeasible);
+    assert_eq!(report.statistics.nodes, 0);
+
+    // Time-tabling alone needs to branch before it detects the conflict
+    let (mut m, starts) = build();
+    m.cumulative(&starts, &[2, 2, 2], &[1, 1, 1], 1);
+
+    let report = m.solve_and_report();
+    assert_eq!(report.status, SearchStatus::Infeasible);
+    assert!(report.statistics.nodes > 0);
+}
+
+#[test]
+fn disjunctive() {
+    let mut m = Model::default();
+
+    let starts: Vec<_> = m.new_vars(3, 0, 10).unwrap().collect();
+    let end = m.new_var(0, 20).unwrap();
+
+    let durations = [3, 2, 4];
+
+    for (&s, &d) in starts.iter().zip(&durations) {
+        m.less_than_or_equals(s.plus(d), end);
+    }
+
+    m.disjunctive(&starts, &durations);
+
+    // Tasks run one after the other, in any order
+    let solution = m.minimize(end).unwrap();
+    assert_eq!(solution[end], 9);
+}
+
+#[test]
+fn disjunctive_offsets() {
+    let mut m = Model::default();
+
+    let x = m.new_var(0, 5).unwrap();
+    let y = m.new_var(0, 5).unwrap();
+    let z = m.new_var(0, 5).unwrap();
+
+    // Second task starts two units after its variable, third one takes no time at all
+    m.disjunctive(&[x.plus(0), y.plus(2), z.plus(0)], &[3, 3, 0]);
+    m.equals(x, 1);
+    m.equals(z, 2);
+
+    let solutions: Vec<_> = m.enumerate().map(|s| s[y]).collect();
+    assert_eq!(solutions, vec![2, 3, 4, 5]);
+}
+
+#[test]
+fn disjunctive_overload() {
+    let build = || {
+        let mut m = Model::default();
+
+        let a = m.new_var(0, 2).unwrap();
+        let b = m.new_var(0, 2).unwrap();
+        let c = m.new_var(0, 3).unwrap();
+
+        (m, [a, b, c])
+    };
+
+    // Three tasks cannot fit in window `[0, 5]`
+    let (mut m, starts) = build();
+    m.disjunctive(&starts, &[2, 2, 2]);
+
+    let report = m.solve_and_report();
+    assert_eq!(report.status, SearchStatus::Infeasible);
+    assert_eq!(report.statistics.nodes, 0);
+
+    // Time-tabling with a unit capacity needs to branch before it detects the conflict
+    let (mut m, starts) = build();
+    m.cumulative(&starts, &[2, 2, 2], &[1, 1, 1], 1);
+
+    let report = m.solve_and_report();
+    assert_eq!(report.status, SearchStatus::Infeasible);
+    assert!(report.statistics.nodes > 0);
+}
+
+#[test]
+fn disjunctive_job_shop() {
+    // Operations of each job, as machine and duration, run in order
+    let jobs: [&[(usize, i32)]; 3] = [
+        &[(0, 3), (1, 2), (2, 2)],
+        &[(0, 2), (2, 1), (1, 4)],
+        &[(1, 4), (2, 3)],
+    ];
+
+    let mut m = Model::default();
+
+    let end = m.new_var(0, 30).unwrap();
+    let mut machines = vec![(Vec::new(), Vec::new()); 3];
+
+    for job in jobs {
+        let starts: Vec<_> = m.new_vars(job.len(), 0, 30).unwrap().collect();
+
+        for (k, &(machine, duration)) in job.iter().enumerate() {
+            machines[machine].0.push(starts[k]);
+            machines[machine].1.push(duration);
+
+            let next = starts.get(k + 1).copied().unwrap_or(end);
+            m.less_than_or_equals(starts[k].plus(duration), next);
+        }
+    }
+
+    for (starts, durations) in &machines {
+        m.disjunctive(starts, durations);
+    }
+
+    let solution = m.minimize(end).unwrap();
+    assert_eq!(solution[end], 11);
+}
+
+#[test]
+fn circuit() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 0, 3).unwrap().collect();
+    m.circuit(&xs);
+
+    // Every solution is a single cycle of all nodes, starting from the first one
+    let solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&xs)).collect();
+    assert_eq!(solutions.len(), 6);
+
+    for successors in solutions {
+        let mut node = 0;
+
+        for _ in 0..3 {
+            node = usize::try_from(successors[node]).unwrap();
+            assert_ne!(node, 0);
+        }
+
+        assert_eq!(successors[node], 0);
+    }
+}
+
+#[test]
+fn circuit_subtour() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 0, 3).unwrap().collect();
+    m.circuit(&xs);
+
+    // First two nodes would close a cycle without the others
+    m.equals(xs[0], 1);
+    m.equals(xs[1], 0);
+
+    let report = m.solve_and_report();
+    assert_eq!(report.status, SearchStatus::Infeasible);
+    assert_eq!(report.statistics.nodes, 0);
+}
+
+#[test]
+fn circuit_components() {
+    let mut m = Model::default();
+
+    // No arc leads from the last two nodes back to the first two
+    let xs = [
+        m.new_var(0, 3).unwrap(),
+        m.new_var(0, 3).unwrap(),
+        m.new_var(2, 3).unwrap(),
+        m.new_var(2, 3).unwrap(),
+    ];
+
+    m.circuit(&xs);
+
+    let report = m.solve_and_report();
+    assert_eq!(report.status, SearchStatus::Infeasible);
+    assert_eq!(report.statistics.nodes, 0);
+}
+
+#[test]
+fn circuit_tsp() {
+    // Cities along a line, where a tour goes to the end and back
+    let distances = [[0, 1, 2, 3], [1, 0, 1, 2], [2, 1, 0, 1], [3, 2, 1, 0]];
+
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(4, 0, 3).unwrap().collect();
+    m.circuit(&xs);
+
+    let costs: Vec<_> = xs
+        .iter()
+        .zip(&distances)
+        .map(|(&x, row)| m.element(x, row))
+        .collect();
+
+    let total = m.sum(&costs);
+
+    let solution = m.minimize(total).unwrap();
+    assert_eq!(solution[total], 6);
+}
+
+#[test]
+fn subcircuit() {
+    let mut m = Model::default();
+
+    let xs: Vec<_> = m.new_vars(3, 0, 2).unwrap().collect();
+    m.subcircuit(&xs);
+
+    // No cycle, three cycles of two nodes, and two cycles of all nodes
+    assert_eq!(m.enumerate().count(), 6);
+}
+
+#[test]
+fn subcircuit_mandatory() {
+    let mut m = Model::default();
+
+    // First node cannot loop, which rules out leaving all nodes out of the cycle
+    let x = m.new_var(1, 2).unwrap();
+    let y = m.new_var(0, 2).unwrap();
+    let z = m.new_var(1, 2).unwrap();
+
+    m.subcircuit(&[x, y, z]);
+
+    let solutions: Vec<_> = m.enumerate().map(|s| s.get_values(&[x, y, z])).collect();
+    assert_eq!(solutions, vec![vec![1, 0, 2], vec![2, 0, 1]]);
+}
@@ -3,14 +3,41 @@ use core::ops::{Index, IndexMut};
 use crate::props::PropId;
 use crate::solution::Solution;
 
-/// Domain for a decision variable, tracked as an interval of integers.
+/// Domain for a decision variable, tracked as an interval of integers with optional holes.
 #[derive(Clone, Debug)]
 pub struct Var {
     pub min: i32,
     pub max: i32,
+
+    /// Sorted and disjoint intervals of removed values, strictly between `min` and `max`.
+    holes: Vec<(i32, i32)>,
 }
 
 impl Var {
+    /// Create a domain with all values between the provided bounds, both included.
+    pub const fn with_bounds(min: i32, max: i32) -> Self {
+        Self {
+            min,
+            max,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Create a domain that only contains the provided values, which must be sorted and unique.
+    pub fn with_values(values: &[i32]) -> Self {
+        let holes = values
+            .windows(2)
+            .filter(|w| w[1] - w[0] > 1)
+            .map(|w| (w[0] + 1, w[1] - 1))
+            .collect();
+
+        Self {
+            min: values[0],
+            max: values[values.len() - 1],
+            holes,
+        }
+    }
+
     /// Assigned variables have a domain reduced to a singleton.
     pub const fn is_assigned(&self) -> bool {
         self.min == self.max
@@ -31,6 +58,153 @@ impl Var {
 
         self.min
     }
+
+    /// Number of values in the domain.
+    pub fn size(&self) -> u64 {
+        let width = |min: i32, max: i32| u64::from(max.abs_diff(min)) + 1;
+
+        let n_removed: u64 = self
+            .holes
+            .iter()
+            .map(|&(start, end)| width(start, end))
+            .sum();
+
+        width(self.min, self.max) - n_removed
+    }
+
+    /// Determine if the provided value belongs to the domain.
+    pub fn contains(&self, value: i32) -> bool {
+        self.min <= value && value <= self.max && self.find_hole(value).is_err()
+    }
+
+    /// Iterate over the values of the domain, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = i32> + '_ {
+        let starts = core::iter::once(self.min).chain(self.holes.iter().map(|&(_, end)| end + 1));
+        let ends = self.holes.iter().map(|&(start, _)| start - 1);
+
+        starts
+            .zip(ends.chain(core::iter::once(self.max)))
+            .flat_map(|(min, max)| min..=max)
+    }
+
+    /// Raise domain minimum, skipping over removed values.
+    ///
+    /// This function assumes that `self.min < min <= self.max`.
+    pub fn set_min(&mut self, min: i32) {
+        // Holes entirely below the new minimum are no longer relevant
+        let n_below = self.holes.partition_point(|&(_, end)| end < min);
+        drop(self.holes.drain(..n_below));
+
+        self.min = min;
+
+        // New minimum may fall inside a hole, in which case it gets pushed past it
+        if let Some(&(start, end)) = self.holes.first() {
+            if start <= min {
+                self.min = end + 1;
+                let _hole = self.holes.remove(0);
+            }
+        }
+    }
+
+    /// Lower domain maximum, skipping over removed values.
+    ///
+    /// This function assumes that `self.min <= max < self.max`.
+    pub fn set_max(&mut self, max: i32) {
+        // Holes entirely above the new maximum are no longer relevant
+        let n_kept = self.holes.partition_point(|&(start, _)| start <= max);
+        self.holes.truncate(n_kept);
+
+        self.max = max;
+
+        // New maximum may fall inside a hole, in which case it gets pulled before it
+        if let Some(&(start, end)) = self.holes.last() {
+            if max <= end {
+                self.max = start - 1;
+                let _hole = self.holes.pop();
+            }
+        }
+    }
+
+    /// Remove a value from the domain.
+    ///
+    /// This function assumes that `self.min < value < self.max`, and that value is in the domain.
+    pub fn remove(&mut self, value: i32) {
+        let Err(i) = self.find_hole(value) else {
+            return;
+        };
+
+        // Merge new hole with its neighbors when they are adjacent
+        let merges_prev = i > 0 && self.holes[i - 1].1 + 1 == value;
+        let merges_next = i < self.holes.len() && self.holes[i].0 - 1 == value;
+
+        match (merges_prev, merges_next) {
+            (true, true) => {
+                self.holes[i - 1].1 = self.holes[i].1;
+                let _hole = self.holes.remove(i);
+            }
+            (true, false) => self.holes[i - 1].1 = value,
+            (false, true) => self.holes[i].0 = value,
+            (false, false) => self.holes.insert(i, (value, value)),
+        }
+    }
+
+    /// Search for the hole that contains the provided value, or where a new one would be inserted.
+    fn find_hole(&self, value: i32) -> Result<usize, usize> {
+        self.holes.binary_search_by(|&(start, end)| {
+            if end < value {
+                core::cmp::Ordering::Less
+            } else if start > value {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+    }
+}
+
+/// Read-only access to the domain of a decision variable.
+#[derive(Clone, Copy, Debug)]
+pub struct Domain<'a>(&'a Var);
+
+impl<'a> Domain<'a> {
+    pub(crate) const fn new(var: &'a Var) -> Self {
+        Self(var)
+    }
+
+    /// Lowest value of the domain.
+    #[must_use]
+    pub const fn min(self) -> i32 {
+        self.0.min
+    }
+
+    /// Highest value of the domain.
+    #[must_use]
+    pub const fn max(self) -> i32 {
+        self.0.max
+    }
+
+    /// Midpoint between domain bounds, rounded towards the minimum.
+    #[must_use]
+    pub const fn mid(self) -> i32 {
+        self.0.mid()
+    }
+
+    /// Number of values in the domain.
+    #[must_use]
+    pub fn size(self) -> u64 {
+        self.0.size()
+    }
+
+    /// Determine if the provided value belongs to the domain.
+    #[must_use]
+    pub fn contains(self, value: i32) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Iterate over the values of the domain, in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = i32> + 'a {
+        self.0.iter()
+    }
 }
 
 /// Store decision variables and expose a limited interface to operate on them.
@@ -40,9 +214,19 @@ pub struct Vars(Vec<Var>);
 impl Vars {
     /// Create a new decision variable.
     pub fn new_var_with_bounds(&mut self, min: i32, max: i32) -> VarId {
+        self.new_var_with_domain(Var::with_bounds(min, max))
+    }
+
+    /// Create a new decision variable, restricted to the provided sorted and unique values.
+    pub fn new_var_with_values(&mut self, values: &[i32]) -> VarId {
+        self.new_var_with_domain(Var::with_values(values))
+    }
+
+    /// Store the provided domain and create a handle to refer to it.
+    fn new_var_with_domain(&mut self, var: Var) -> VarId {
         let v = VarId(self.0.len());
 
-        self.0.push(Var { min, max });
+        self.0.push(var);
 
         v
     }
@@ -52,6 +236,15 @@ impl Vars {
         self.0.iter().position(|var| !var.is_assigned()).map(VarId)
     }
 
+    /// List handles to all unassigned decision variables, in creation order.
+    pub fn get_unassigned_vars_iter(&self) -> impl Iterator<Item = VarId> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, var)| !var.is_assigned())
+            .map(|(i, _)| VarId(i))
+    }
+
     /// Determine if all decision variables are assigned.
     pub fn is_assigned_all(&self) -> bool {
         self.get_unassigned_var().is_none()
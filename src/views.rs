@@ -18,6 +18,16 @@ pub trait View: ViewRaw {
         self.max_raw(ctx.vars)
     }
 
+    /// Determine if the provided value belongs to the domain.
+    fn contains(self, value: i32, ctx: &Context) -> bool {
+        self.contains_raw(value, ctx.vars)
+    }
+
+    /// Iterate over the values of the domain, in increasing order.
+    fn iter_values(self, ctx: &Context) -> impl Iterator<Item = i32> {
+        self.values_raw(ctx.vars).into_iter()
+    }
+
     /// Try to set the provided value as domain minimum, failing the search space on infeasibility.
     ///
     /// The `None` case signals failure, otherwise the new minimum is returned.
@@ -27,6 +37,11 @@ pub trait View: ViewRaw {
     ///
     /// The `None` case signals failure, otherwise the new maximum is returned.
     fn try_set_max(self, max: i32, ctx: &mut Context) -> Option<i32>;
+
+    /// Try to remove the provided value from the domain, failing the search space on infeasibility.
+    ///
+    /// The `None` case signals failure. Removing a value outside of the domain has no effect.
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()>;
 }
 
 /// Extension trait to provide helper methods on views.
@@ -82,12 +97,32 @@ impl<V: View> ViewExt for V {
 pub struct Context<'s> {
     vars: &'s mut Vars,
     events: &'s mut Vec<VarId>,
+
+    /// Trigger variables replaced by the running propagator, as pairs of old and new variable.
+    trigger_moves: Vec<(VarId, VarId)>,
 }
 
 impl<'s> Context<'s> {
     /// Initialize context from mutable references to outside objects.
     pub(crate) fn new(vars: &'s mut Vars, events: &'s mut Vec<VarId>) -> Self {
-        Self { vars, events }
+        Self {
+            vars,
+            events,
+            trigger_moves: Vec::new(),
+        }
+    }
+
+    /// Release context, and extract trigger variables replaced by the running propagator.
+    pub(crate) fn into_trigger_moves(self) -> Vec<(VarId, VarId)> {
+        self.trigger_moves
+    }
+
+    /// Schedule the running propagator on changes to `to`, instead of changes to `from`.
+    ///
+    /// Propagators that only depend on a few of their variables at a time, such as clauses with
+    /// watched literals, use this to avoid being scheduled by changes they would ignore anyway.
+    pub fn move_trigger_var(&mut self, from: VarId, to: VarId) {
+        self.trigger_moves.push((from, to));
     }
 
     /// Try to set provided value as domain maximum, failing the space on infeasibility.
@@ -101,8 +136,8 @@ impl<'s> Context<'s> {
         }
 
         if min > var.min {
-            // Set new minimum
-            var.min = min;
+            // Set new minimum, which may be raised further to skip removed values
+            var.set_min(min);
 
             // Record modification event
             self.events.push(v);
@@ -111,6 +146,11 @@ impl<'s> Context<'s> {
         Some(var.min)
     }
 
+    /// Iterate over the values of the domain of a decision variable, in increasing order.
+    pub fn iter_values(&self, v: VarId) -> impl Iterator<Item = i32> + '_ {
+        self.vars[v].iter()
+    }
+
     /// Try to set provided value as domain maximum, failing the space on infeasibility.
     pub fn try_set_max(&mut self, v: VarId, max: i32) -> Option<i32> {
         let var = &mut self.vars[v];
@@ -121,8 +161,8 @@ impl<'s> Context<'s> {
         }
 
         if max < var.max {
-            // Set new maximum
-            var.max = max;
+            // Set new maximum, which may be lowered further to skip removed values
+            var.set_max(max);
 
             // Record modification event
             self.events.push(v);
@@ -130,10 +170,39 @@ impl<'s> Context<'s> {
 
         Some(var.max)
     }
+
+    /// Try to remove provided value from domain, failing the space on infeasibility.
+    pub fn try_remove_value(&mut self, v: VarId, value: i32) -> Option<()> {
+        let var = &mut self.vars[v];
+
+        // Values outside of the domain are already removed
+        if !var.contains(value) {
+            return Some(());
+        }
+
+        // Infeasible, fail space
+        if var.is_assigned() {
+            return None;
+        }
+
+        // Bounds are updated directly to keep domain endpoints in sync with holes
+        if value == var.min {
+            var.set_min(value + 1);
+        } else if value == var.max {
+            var.set_max(value - 1);
+        } else {
+            var.remove(value);
+        }
+
+        // Record modification event
+        self.events.push(v);
+
+        Some(())
+    }
 }
 
 // Trait kept internal, to prevent users from declaring their own views.
-pub(crate) trait ViewRaw: Copy + core::fmt::Debug + 'static {
+pub(crate) trait ViewRaw: Copy + core::fmt::Debug + Send + Sync + 'static {
     /// Get the handle of the variable this view depends on.
     fn get_underlying_var_raw(self) -> Option<VarId>;
 
@@ -142,6 +211,12 @@ pub(crate) trait ViewRaw: Copy + core::fmt::Debug + 'static {
 
     /// Access domain maximum.
     fn max_raw(self, vars: &Vars) -> i32;
+
+    /// Determine if the provided value belongs to the domain.
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool;
+
+    /// List the values of the domain, in increasing order.
+    fn values_raw(self, vars: &Vars) -> Vec<i32>;
 }
 
 impl ViewRaw for i32 {
@@ -156,12 +231,20 @@ impl ViewRaw for i32 {
     fn max_raw(self, _vars: &Vars) -> i32 {
         self
     }
+
+    fn contains_raw(self, value: i32, _vars: &Vars) -> bool {
+        value == self
+    }
+
+    fn values_raw(self, _vars: &Vars) -> Vec<i32> {
+        vec![self]
+    }
 }
 
 impl View for i32 {
     fn try_set_min(self, min: i32, _ctx: &mut Context) -> Option<i32> {
         if min <= self {
-            Some(min)
+            Some(self)
         } else {
             None
         }
@@ -169,11 +252,19 @@ impl View for i32 {
 
     fn try_set_max(self, max: i32, _ctx: &mut Context) -> Option<i32> {
         if max >= self {
-            Some(max)
+            Some(self)
         } else {
             None
         }
     }
+
+    fn try_remove_value(self, value: i32, _ctx: &mut Context) -> Option<()> {
+        if value == self {
+            None
+        } else {
+            Some(())
+        }
+    }
 }
 
 impl ViewRaw for VarId {
@@ -188,6 +279,14 @@ impl ViewRaw for VarId {
     fn max_raw(self, vars: &Vars) -> i32 {
         vars[self].max
     }
+
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool {
+        vars[self].contains(value)
+    }
+
+    fn values_raw(self, vars: &Vars) -> Vec<i32> {
+        vars[self].iter().collect()
+    }
 }
 
 impl View for VarId {
@@ -198,6 +297,10 @@ impl View for VarId {
     fn try_set_max(self, max: i32, ctx: &mut Context) -> Option<i32> {
         ctx.try_set_max(self, max)
     }
+
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()> {
+        ctx.try_remove_value(self, value)
+    }
 }
 
 impl ViewRaw for VarIdBinary {
@@ -212,6 +315,14 @@ impl ViewRaw for VarIdBinary {
     fn max_raw(self, vars: &Vars) -> i32 {
         self.0.max_raw(vars)
     }
+
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool {
+        self.0.contains_raw(value, vars)
+    }
+
+    fn values_raw(self, vars: &Vars) -> Vec<i32> {
+        self.0.values_raw(vars)
+    }
 }
 
 impl View for VarIdBinary {
@@ -222,6 +333,10 @@ impl View for VarIdBinary {
     fn try_set_max(self, max: i32, ctx: &mut Context) -> Option<i32> {
         self.0.try_set_max(max, ctx)
     }
+
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()> {
+        self.0.try_remove_value(value, ctx)
+    }
 }
 
 /// Invert the sign of the bounds of the underlying view.
@@ -240,15 +355,32 @@ impl<V: View> ViewRaw for Opposite<V> {
     fn max_raw(self, vars: &Vars) -> i32 {
         -self.0.min_raw(vars)
     }
+
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool {
+        self.0.contains_raw(-value, vars)
+    }
+
+    fn values_raw(self, vars: &Vars) -> Vec<i32> {
+        self.0
+            .values_raw(vars)
+            .into_iter()
+            .rev()
+            .map(|v| -v)
+            .collect()
+    }
 }
 
 impl<V: View> View for Opposite<V> {
     fn try_set_min(self, min: i32, ctx: &mut Context) -> Option<i32> {
-        self.0.try_set_max(-min, ctx)
+        self.0.try_set_max(-min, ctx).map(|max| -max)
     }
 
     fn try_set_max(self, max: i32, ctx: &mut Context) -> Option<i32> {
-        self.0.try_set_min(-max, ctx)
+        self.0.try_set_min(-max, ctx).map(|min| -min)
+    }
+
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()> {
+        self.0.try_remove_value(-value, ctx)
     }
 }
 
@@ -271,15 +403,30 @@ impl<V: View> ViewRaw for Plus<V> {
     fn max_raw(self, vars: &Vars) -> i32 {
         self.x.max_raw(vars) + self.offset
     }
+
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool {
+        self.x.contains_raw(value - self.offset, vars)
+    }
+
+    fn values_raw(self, vars: &Vars) -> Vec<i32> {
+        let values = self.x.values_raw(vars);
+        values.into_iter().map(|v| v + self.offset).collect()
+    }
 }
 
 impl<V: View> View for Plus<V> {
     fn try_set_min(self, min: i32, ctx: &mut Context) -> Option<i32> {
-        self.x.try_set_min(min - self.offset, ctx)
+        let min = self.x.try_set_min(min - self.offset, ctx)?;
+        Some(min + self.offset)
     }
 
     fn try_set_max(self, max: i32, ctx: &mut Context) -> Option<i32> {
-        self.x.try_set_max(max - self.offset, ctx)
+        let max = self.x.try_set_max(max - self.offset, ctx)?;
+        Some(max + self.offset)
+    }
+
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()> {
+        self.x.try_remove_value(value - self.offset, ctx)
     }
 }
 
@@ -332,6 +479,22 @@ impl<V: View> ViewRaw for Times<V> {
             Self::Pos(pos) => pos.max_raw(vars),
         }
     }
+
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool {
+        match self {
+            Self::Neg(neg) => neg.contains_raw(value, vars),
+            Self::Zero => 0.contains_raw(value, vars),
+            Self::Pos(pos) => pos.contains_raw(value, vars),
+        }
+    }
+
+    fn values_raw(self, vars: &Vars) -> Vec<i32> {
+        match self {
+            Self::Neg(neg) => neg.values_raw(vars),
+            Self::Zero => 0.values_raw(vars),
+            Self::Pos(pos) => pos.values_raw(vars),
+        }
+    }
 }
 
 impl<V: View> View for Times<V> {
@@ -350,6 +513,14 @@ impl<V: View> View for Times<V> {
             Self::Pos(pos) => pos.try_set_max(max, ctx),
         }
     }
+
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()> {
+        match self {
+            Self::Neg(neg) => neg.try_remove_value(value, ctx),
+            Self::Zero => 0.try_remove_value(value, ctx),
+            Self::Pos(pos) => pos.try_remove_value(value, ctx),
+        }
+    }
 }
 
 /// Scale the underlying view by a strictly positive constant factor.
@@ -378,15 +549,35 @@ impl<V: View> ViewRaw for TimesPos<V> {
     fn max_raw(self, vars: &Vars) -> i32 {
         self.x.max_raw(vars) * self.scale_pos
     }
+
+    fn contains_raw(self, value: i32, vars: &Vars) -> bool {
+        value % self.scale_pos == 0 && self.x.contains_raw(value / self.scale_pos, vars)
+    }
+
+    fn values_raw(self, vars: &Vars) -> Vec<i32> {
+        let values = self.x.values_raw(vars);
+        values.into_iter().map(|v| v * self.scale_pos).collect()
+    }
 }
 
 impl<V: View> View for TimesPos<V> {
     fn try_set_min(self, min: i32, ctx: &mut Context) -> Option<i32> {
-        self.x.try_set_min(min.div_ceil(self.scale_pos), ctx)
+        let min = self.x.try_set_min(min.div_ceil(self.scale_pos), ctx)?;
+        Some(min * self.scale_pos)
     }
 
     fn try_set_max(self, max: i32, ctx: &mut Context) -> Option<i32> {
-        self.x.try_set_max(max.div_floor(self.scale_pos), ctx)
+        let max = self.x.try_set_max(max.div_floor(self.scale_pos), ctx)?;
+        Some(max * self.scale_pos)
+    }
+
+    fn try_remove_value(self, value: i32, ctx: &mut Context) -> Option<()> {
+        // Values that are not multiples of the scale are never part of the domain
+        if value % self.scale_pos == 0 {
+            self.x.try_remove_value(value / self.scale_pos, ctx)
+        } else {
+            Some(())
+        }
     }
 }
 